@@ -2,6 +2,9 @@ use std::collections::HashMap;
 
 use koi_ecs::WorldClonableTrait;
 
+mod spline;
+pub use spline::*;
+
 pub trait InterpolateTrait {
     fn interpolate(&self, other: &Self, amount: f32) -> Self;
 }
@@ -266,7 +269,10 @@ pub fn initialize_animation_plugin<T: InterpolateTrait + 'static + Sync + Send>(
         let animations = resources.get::<koi_assets::AssetStore<Animation>>();
         let time = resources.get::<koi_time::Time>();
         let amount_seconds = time.draw_delta_seconds as f32;
-        for (_, animation_player) in world.query::<&mut AnimationPlayer>().iter() {
+        for (_, animation_player) in world
+            .query::<koi_ecs::Without<&mut AnimationPlayer, &koi_ecs::InactiveInHierarchy>>()
+            .iter()
+        {
             animation_player.advance_time(world, &animations, amount_seconds)
         }
     });
@@ -0,0 +1,398 @@
+use kmath::*;
+
+/// A curve made of cubic segments.
+///
+/// `t` values passed to [SplineTrait::position] go from 0.0 at the start to 1.0 at the end
+/// with each segment covering an equal range. Distance along the curve isn't uniform in `t`,
+/// so use an [ArcLengthTable] to move at a constant speed.
+///
+/// A spline without any segments, like one whose points were cleared, stays at the origin.
+pub trait SplineTrait {
+    fn segment_count(&self) -> usize;
+    /// `t` goes from 0.0 to 1.0 within the segment.
+    fn segment_position(&self, segment: usize, t: f32) -> Vec3;
+    fn segment_derivative(&self, segment: usize, t: f32) -> Vec3;
+
+    fn position(&self, t: f32) -> Vec3 {
+        if self.segment_count() == 0 {
+            return Vec3::ZERO;
+        }
+        let (segment, t) = split_t(self.segment_count(), t);
+        self.segment_position(segment, t)
+    }
+
+    /// The normalized direction of the curve.
+    fn tangent(&self, t: f32) -> Vec3 {
+        if self.segment_count() == 0 {
+            return Vec3::ZERO;
+        }
+        let (segment, t) = split_t(self.segment_count(), t);
+        let derivative = self.segment_derivative(segment, t);
+        if derivative.length_squared() == 0.0 {
+            derivative
+        } else {
+            derivative.normalized()
+        }
+    }
+
+    /// The `t` of the point on the curve closest to `point`.
+    fn closest_t(&self, point: Vec3) -> f32 {
+        let distance_squared = |t: f32| (self.position(t) - point).length_squared();
+
+        // Find the closest of evenly spaced samples then refine around it.
+        let samples = self.segment_count() * 16;
+        if samples == 0 {
+            return 0.0;
+        }
+        let step = 1.0 / samples as f32;
+        let closest_sample = (0..=samples)
+            .map(|i| i as f32 * step)
+            .min_by(|a, b| distance_squared(*a).total_cmp(&distance_squared(*b)))
+            .unwrap();
+
+        // Golden-section search
+        let ratio = 0.618_034;
+        let mut low = (closest_sample - step).max(0.0);
+        let mut high = (closest_sample + step).min(1.0);
+        for _ in 0..20 {
+            let a = high - (high - low) * ratio;
+            let b = low + (high - low) * ratio;
+            if distance_squared(a) < distance_squared(b) {
+                high = b;
+            } else {
+                low = a;
+            }
+        }
+        (low + high) / 2.0
+    }
+
+    fn closest_point(&self, point: Vec3) -> Vec3 {
+        self.position(self.closest_t(point))
+    }
+}
+
+/// Splits a `t` for the whole spline into a segment and a `t` within that segment.
+fn split_t(segment_count: usize, t: f32) -> (usize, f32) {
+    if segment_count == 0 {
+        return (0, 0.0);
+    }
+    let t = t.clamp(0.0, 1.0) * segment_count as f32;
+    let segment = (t as usize).min(segment_count - 1);
+    (segment, t - segment as f32)
+}
+
+fn hermite_position(p0: Vec3, m0: Vec3, p1: Vec3, m1: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    p0 * (2.0 * t3 - 3.0 * t2 + 1.0)
+        + m0 * (t3 - 2.0 * t2 + t)
+        + p1 * (-2.0 * t3 + 3.0 * t2)
+        + m1 * (t3 - t2)
+}
+
+fn hermite_derivative(p0: Vec3, m0: Vec3, p1: Vec3, m1: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    p0 * (6.0 * t2 - 6.0 * t)
+        + m0 * (3.0 * t2 - 4.0 * t + 1.0)
+        + p1 * (-6.0 * t2 + 6.0 * t)
+        + m1 * (3.0 * t2 - 2.0 * t)
+}
+
+/// Cubic Bezier segments that share end points.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BezierSpline {
+    /// Laid out as `[point, control, control, point, control, control, point, ...]`.
+    pub points: Vec<Vec3>,
+}
+
+impl BezierSpline {
+    pub fn new(points: Vec<Vec3>) -> Self {
+        assert!(
+            points.len() >= 4 && (points.len() - 1) % 3 == 0,
+            "A Bezier spline needs 3 points per segment plus 1"
+        );
+        Self { points }
+    }
+}
+
+impl SplineTrait for BezierSpline {
+    fn segment_count(&self) -> usize {
+        self.points.len().saturating_sub(1) / 3
+    }
+
+    fn segment_position(&self, segment: usize, t: f32) -> Vec3 {
+        let [a, b, c, d] = [0, 1, 2, 3].map(|i| self.points[segment * 3 + i]);
+        let u = 1.0 - t;
+        a * (u * u * u) + b * (3.0 * u * u * t) + c * (3.0 * u * t * t) + d * (t * t * t)
+    }
+
+    fn segment_derivative(&self, segment: usize, t: f32) -> Vec3 {
+        let [a, b, c, d] = [0, 1, 2, 3].map(|i| self.points[segment * 3 + i]);
+        let u = 1.0 - t;
+        (b - a) * (3.0 * u * u) + (c - b) * (6.0 * u * t) + (d - c) * (3.0 * t * t)
+    }
+}
+
+/// A smooth curve that passes through every point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatmullRomSpline {
+    pub points: Vec<Vec3>,
+    /// Connects the last point back to the first.
+    pub closed: bool,
+}
+
+impl CatmullRomSpline {
+    pub fn new(points: Vec<Vec3>, closed: bool) -> Self {
+        assert!(
+            points.len() >= 2,
+            "A Catmull-Rom spline needs at least 2 points"
+        );
+        Self { points, closed }
+    }
+
+    fn point(&self, index: isize) -> Vec3 {
+        let len = self.points.len() as isize;
+        if self.closed {
+            self.points[index.rem_euclid(len) as usize]
+        } else {
+            self.points[index.clamp(0, len - 1) as usize]
+        }
+    }
+
+    fn segment_hermite(&self, segment: usize) -> (Vec3, Vec3, Vec3, Vec3) {
+        let i = segment as isize;
+        let (p0, p1, p2, p3) = (
+            self.point(i - 1),
+            self.point(i),
+            self.point(i + 1),
+            self.point(i + 2),
+        );
+        (p1, (p2 - p0) * 0.5, p2, (p3 - p1) * 0.5)
+    }
+}
+
+impl SplineTrait for CatmullRomSpline {
+    fn segment_count(&self) -> usize {
+        if self.closed {
+            self.points.len()
+        } else {
+            self.points.len().saturating_sub(1)
+        }
+    }
+
+    fn segment_position(&self, segment: usize, t: f32) -> Vec3 {
+        let (p0, m0, p1, m1) = self.segment_hermite(segment);
+        hermite_position(p0, m0, p1, m1, t)
+    }
+
+    fn segment_derivative(&self, segment: usize, t: f32) -> Vec3 {
+        let (p0, m0, p1, m1) = self.segment_hermite(segment);
+        hermite_derivative(p0, m0, p1, m1, t)
+    }
+}
+
+/// A curve through points with explicit tangents.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HermiteSpline {
+    /// Each point and the tangent of the curve at that point.
+    pub points: Vec<(Vec3, Vec3)>,
+}
+
+impl HermiteSpline {
+    pub fn new(points: Vec<(Vec3, Vec3)>) -> Self {
+        assert!(
+            points.len() >= 2,
+            "A Hermite spline needs at least 2 points"
+        );
+        Self { points }
+    }
+}
+
+impl SplineTrait for HermiteSpline {
+    fn segment_count(&self) -> usize {
+        self.points.len().saturating_sub(1)
+    }
+
+    fn segment_position(&self, segment: usize, t: f32) -> Vec3 {
+        let ((p0, m0), (p1, m1)) = (self.points[segment], self.points[segment + 1]);
+        hermite_position(p0, m0, p1, m1, t)
+    }
+
+    fn segment_derivative(&self, segment: usize, t: f32) -> Vec3 {
+        let ((p0, m0), (p1, m1)) = (self.points[segment], self.points[segment + 1]);
+        hermite_derivative(p0, m0, p1, m1, t)
+    }
+}
+
+/// Any of the spline types, for storing in components and animations.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Spline {
+    Bezier(BezierSpline),
+    CatmullRom(CatmullRomSpline),
+    Hermite(HermiteSpline),
+}
+
+impl From<BezierSpline> for Spline {
+    fn from(spline: BezierSpline) -> Self {
+        Self::Bezier(spline)
+    }
+}
+
+impl From<CatmullRomSpline> for Spline {
+    fn from(spline: CatmullRomSpline) -> Self {
+        Self::CatmullRom(spline)
+    }
+}
+
+impl From<HermiteSpline> for Spline {
+    fn from(spline: HermiteSpline) -> Self {
+        Self::Hermite(spline)
+    }
+}
+
+impl SplineTrait for Spline {
+    fn segment_count(&self) -> usize {
+        match self {
+            Self::Bezier(s) => s.segment_count(),
+            Self::CatmullRom(s) => s.segment_count(),
+            Self::Hermite(s) => s.segment_count(),
+        }
+    }
+
+    fn segment_position(&self, segment: usize, t: f32) -> Vec3 {
+        match self {
+            Self::Bezier(s) => s.segment_position(segment, t),
+            Self::CatmullRom(s) => s.segment_position(segment, t),
+            Self::Hermite(s) => s.segment_position(segment, t),
+        }
+    }
+
+    fn segment_derivative(&self, segment: usize, t: f32) -> Vec3 {
+        match self {
+            Self::Bezier(s) => s.segment_derivative(segment, t),
+            Self::CatmullRom(s) => s.segment_derivative(segment, t),
+            Self::Hermite(s) => s.segment_derivative(segment, t),
+        }
+    }
+}
+
+/// Maps distance along a spline to `t` so the spline can be traversed at a constant speed.
+///
+/// Must be rebuilt if the spline changes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArcLengthTable {
+    /// The distance along the spline at evenly spaced `t` values.
+    distances: Vec<f32>,
+}
+
+impl ArcLengthTable {
+    /// More samples are more accurate. 16 per segment is usually enough.
+    pub fn new(spline: &(impl SplineTrait + ?Sized), samples_per_segment: usize) -> Self {
+        let samples = (spline.segment_count() * samples_per_segment).max(1);
+        let mut distances = Vec::with_capacity(samples + 1);
+        let mut distance = 0.0;
+        let mut previous = spline.position(0.0);
+        distances.push(0.0);
+        for i in 1..=samples {
+            let position = spline.position(i as f32 / samples as f32);
+            distance += (position - previous).length();
+            distances.push(distance);
+            previous = position;
+        }
+        Self { distances }
+    }
+
+    pub fn length(&self) -> f32 {
+        *self.distances.last().unwrap()
+    }
+
+    /// The `t` that's `distance` along the spline. Clamped to the ends of the spline.
+    pub fn t_at_distance(&self, distance: f32) -> f32 {
+        let distance = distance.clamp(0.0, self.length());
+        let index = self
+            .distances
+            .partition_point(|d| *d < distance)
+            .clamp(1, self.distances.len() - 1);
+        let (d0, d1) = (self.distances[index - 1], self.distances[index]);
+        let fraction = if d1 > d0 {
+            (distance - d0) / (d1 - d0)
+        } else {
+            0.0
+        };
+        (index as f32 - 1.0 + fraction) / (self.distances.len() - 1) as f32
+    }
+
+    pub fn distance_at_t(&self, t: f32) -> f32 {
+        let samples = (self.distances.len() - 1) as f32;
+        let t = t.clamp(0.0, 1.0) * samples;
+        let index = (t as usize).min(self.distances.len() - 2);
+        let (d0, d1) = (self.distances[index], self.distances[index + 1]);
+        d0 + (d1 - d0) * (t - index as f32)
+    }
+}
+
+/// Animates a [Vec3] property along a spline, reaching the end of each segment at a key frame.
+///
+/// There should be one more key frame than [SplineTrait::segment_count].
+pub struct SplineAnimationClip {
+    pub set_property: for<'a> fn(&'a koi_ecs::EntityRef, v: Vec3),
+    pub key_frames: Vec<f32>,
+    pub spline: Spline,
+}
+
+impl crate::TypedAnimationClipTrait for SplineAnimationClip {
+    fn length(&self) -> f32 {
+        self.key_frames.last().copied().unwrap_or(0.0)
+    }
+
+    fn key_frame_count(&self) -> usize {
+        self.key_frames.len()
+    }
+
+    fn animate_entity(
+        &self,
+        entity: &koi_ecs::EntityRef,
+        animation_curve: fn(f32) -> f32,
+        time: f32,
+    ) {
+        let index = self.key_frames.partition_point(|k| *k <= time);
+        let segment = index
+            .saturating_sub(1)
+            .min(self.spline.segment_count().saturating_sub(1));
+
+        let t = match (
+            self.key_frames.get(segment),
+            self.key_frames.get(segment + 1),
+        ) {
+            (Some(k0), Some(k1)) if k1 > k0 => ((time - k0) / (k1 - k0)).clamp(0.0, 1.0),
+            _ => 0.0,
+        };
+        (self.set_property)(
+            entity,
+            self.spline.segment_position(segment, (animation_curve)(t)),
+        )
+    }
+}
+
+#[test]
+fn spline_arc_length_test() {
+    let spline = CatmullRomSpline::new(vec![Vec3::ZERO, Vec3::X, Vec3::X * 3.0], false);
+    assert_eq!(spline.position(0.5), Vec3::X);
+    assert_eq!(spline.position(1.0), Vec3::X * 3.0);
+
+    let arc_lengths = ArcLengthTable::new(&spline, 16);
+    assert!((arc_lengths.length() - 3.0).abs() < 0.001);
+    let halfway = spline.position(arc_lengths.t_at_distance(1.5));
+    assert!((halfway - Vec3::X * 1.5).length() < 0.01);
+
+    let closest = spline.closest_point(Vec3::new(2.0, 1.0, 0.0));
+    assert!((closest - Vec3::X * 2.0).length() < 0.01);
+}
+
+#[test]
+fn empty_spline_test() {
+    let spline = BezierSpline { points: Vec::new() };
+    assert_eq!(spline.segment_count(), 0);
+    assert_eq!(spline.position(0.5), Vec3::ZERO);
+    assert_eq!(ArcLengthTable::new(&spline, 16).length(), 0.0);
+}
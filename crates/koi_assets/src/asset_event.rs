@@ -0,0 +1,40 @@
+use crate::{AssetError, WeakHandle};
+
+/// Something that happened to an asset in an [crate::AssetStore].
+///
+/// Read these with [crate::AssetStore::events] to react when assets change,
+/// for example to restart a sound once it finishes loading.
+pub enum AssetEvent<Asset: 'static> {
+    /// The asset finished loading and replaced its placeholder.
+    Loaded(WeakHandle<Asset>),
+    /// An already loaded asset was replaced, either by hot reloading or [crate::AssetStore::replace].
+    Reloaded(WeakHandle<Asset>),
+    Failed(WeakHandle<Asset>, AssetError),
+    /// Every [crate::Handle] to the asset was dropped and it was removed.
+    Dropped(WeakHandle<Asset>),
+}
+
+impl<Asset> AssetEvent<Asset> {
+    /// The asset this event is about.
+    pub fn handle(&self) -> &WeakHandle<Asset> {
+        match self {
+            Self::Loaded(handle)
+            | Self::Reloaded(handle)
+            | Self::Failed(handle, _)
+            | Self::Dropped(handle) => handle,
+        }
+    }
+}
+
+impl<Asset> std::fmt::Debug for AssetEvent<Asset> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Loaded(handle) => f.debug_tuple("Loaded").field(handle).finish(),
+            Self::Reloaded(handle) => f.debug_tuple("Reloaded").field(handle).finish(),
+            Self::Failed(handle, error) => {
+                f.debug_tuple("Failed").field(handle).field(error).finish()
+            }
+            Self::Dropped(handle) => f.debug_tuple("Dropped").field(handle).finish(),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! Polls the modification times of files that assets were loaded from.
+//!
+//! Hot reloading is opt-in. Add a [HotReload] resource to enable it:
+//! each [crate::AssetStore] then checks its files when `finalize_asset_loads` is called
+//! and reloads only the assets whose files changed.
+
+/// Add this as a resource to enable hot reloading for all [crate::AssetStore]s.
+pub struct HotReload {
+    /// How often each [crate::AssetStore] checks its files for changes.
+    pub poll_interval_seconds: f32,
+}
+
+impl Default for HotReload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HotReload {
+    pub fn new() -> Self {
+        Self {
+            poll_interval_seconds: 0.5,
+        }
+    }
+}
+
+thread_local! {
+    /// Files reported with [watch_file] by the asset being finalized on this thread.
+    static WATCHED_FILES: std::cell::RefCell<Vec<String>> = std::cell::RefCell::new(Vec::new());
+}
+
+/// Reports that the asset currently being finalized was also read from `path`,
+/// like a glTF's buffers.
+///
+/// With hot reloading the asset is reloaded when any of its files change.
+/// Call this from an [crate::AssetStore]'s `handle_result` function.
+pub fn watch_file(path: &str) {
+    WATCHED_FILES.with(|files| files.borrow_mut().push(koi_fetch::normalize_path(path)));
+}
+
+/// Runs `f` and returns the files it reported with [watch_file].
+pub(crate) fn with_watched_files<R>(f: impl FnOnce() -> R) -> (R, Vec<String>) {
+    let previous = WATCHED_FILES.with(|files| files.take());
+    let result = f();
+    let watched_files = WATCHED_FILES.with(|files| files.replace(previous));
+    (result, watched_files)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct WatchedFile {
+    /// The file on disk the path resolved to when it was first checked.
+    file: Option<String>,
+    modified: Option<std::time::SystemTime>,
+    checked: bool,
+}
+
+/// Tracks file modification times for a single [crate::AssetStore].
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) struct FileWatcher {
+    files: std::collections::HashMap<String, WatchedFile>,
+    last_poll: Option<std::time::Instant>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileWatcher {
+    pub fn new() -> Self {
+        Self {
+            files: std::collections::HashMap::new(),
+            last_poll: None,
+        }
+    }
+
+    /// Returns `false` if less than `poll_interval_seconds` has passed since the last poll.
+    pub fn should_poll(&mut self, hot_reload: &HotReload) -> bool {
+        let now = std::time::Instant::now();
+        if let Some(last_poll) = self.last_poll {
+            if (now - last_poll).as_secs_f32() < hot_reload.poll_interval_seconds {
+                return false;
+            }
+        }
+        self.last_poll = Some(now);
+        true
+    }
+
+    /// Returns `true` if `path` changed since it was last checked.
+    /// The first check of a path records its modification time and returns `false`.
+    /// Paths that don't resolve to a file on disk never change.
+    pub fn file_changed(&mut self, path: &str) -> bool {
+        let modified_time = |file: &Option<String>| {
+            file.as_ref()
+                .and_then(|file| std::fs::metadata(file).and_then(|m| m.modified()).ok())
+        };
+        match self.files.get_mut(path) {
+            Some(watched) => {
+                watched.checked = true;
+                let modified = modified_time(&watched.file);
+                let changed = watched.modified != modified && modified.is_some();
+                watched.modified = modified;
+                changed
+            }
+            None => {
+                let file = koi_fetch::resolve_file_path(path);
+                self.files.insert(
+                    path.into(),
+                    WatchedFile {
+                        modified: modified_time(&file),
+                        file,
+                        checked: true,
+                    },
+                );
+                false
+            }
+        }
+    }
+
+    /// Stops tracking files that weren't checked since the last call,
+    /// because nothing loaded from them anymore.
+    pub fn remove_unchecked(&mut self) {
+        self.files
+            .retain(|_, watched| std::mem::take(&mut watched.checked));
+    }
+}
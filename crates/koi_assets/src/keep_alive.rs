@@ -0,0 +1,136 @@
+use crate::{AssetStoreInner, AssetTrait, Handle, LoadState};
+
+/// Memory used by the assets in an [crate::AssetStore], as reported by [AssetTrait::byte_size].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct MemoryStats {
+    /// Every asset, including the placeholder.
+    pub asset_count: usize,
+    /// The total size of assets that report a size.
+    pub bytes: usize,
+    /// Assets that don't report a size and aren't included in `bytes`.
+    pub unknown_size_count: usize,
+    /// Unused assets kept alive by the cache set with [crate::AssetStore::set_cache_budget].
+    pub cached_count: usize,
+    pub cached_bytes: usize,
+}
+
+pub(crate) fn memory_stats<Asset: AssetTrait>(
+    asset_store_inner: &AssetStoreInner<Asset>,
+    keep_alive: Option<&KeepAliveCache<Asset>>,
+) -> MemoryStats {
+    let mut stats = MemoryStats::default();
+    for asset in asset_store_inner.items_iter() {
+        stats.asset_count += 1;
+        match asset.byte_size() {
+            Some(bytes) => stats.bytes += bytes,
+            None => stats.unknown_size_count += 1,
+        }
+    }
+    if let Some(keep_alive) = keep_alive {
+        for entry in keep_alive.entries.iter().filter(|e| e.is_unused()) {
+            stats.cached_count += 1;
+            stats.cached_bytes += asset_store_inner
+                .get(&entry.handle)
+                .byte_size()
+                .unwrap_or(0);
+        }
+    }
+    stats
+}
+
+struct KeepAliveEntry<Asset: 'static> {
+    handle: Handle<Asset>,
+    last_used_frame: u64,
+}
+
+impl<Asset> KeepAliveEntry<Asset> {
+    /// `true` if the cache holds the only [Handle].
+    fn is_unused(&self) -> bool {
+        self.handle
+            .drop_handle
+            .as_ref()
+            .is_some_and(|drop_handle| std::sync::Arc::strong_count(drop_handle) == 1)
+    }
+}
+
+/// Holds a [Handle] to every path-loaded asset so that unused assets stay loaded
+/// until the total size of unused assets exceeds the budget.
+pub(crate) struct KeepAliveCache<Asset: 'static> {
+    pub budget_bytes: usize,
+    entries: Vec<KeepAliveEntry<Asset>>,
+    frame: u64,
+}
+
+impl<Asset: AssetTrait> KeepAliveCache<Asset> {
+    pub fn new(budget_bytes: usize) -> Self {
+        Self {
+            budget_bytes,
+            entries: Vec::new(),
+            frame: 0,
+        }
+    }
+
+    pub fn keep_alive(&mut self, handle: &Handle<Asset>) {
+        if handle.drop_handle.is_none() || self.entries.iter().any(|e| e.handle == *handle) {
+            return;
+        }
+        self.entries.push(KeepAliveEntry {
+            handle: handle.clone(),
+            last_used_frame: self.frame,
+        });
+    }
+
+    /// Releases the least recently used assets until unused assets fit in the budget.
+    pub fn evict(&mut self, asset_store_inner: &AssetStoreInner<Asset>) {
+        self.frame += 1;
+
+        let mut unused_bytes = 0;
+        let frame = self.frame;
+        self.entries.retain_mut(|entry| {
+            if !entry.is_unused() {
+                entry.last_used_frame = frame;
+                return true;
+            }
+            // Assets that are still loading or can't be measured aren't worth keeping.
+            if asset_store_inner.load_state(&entry.handle) != LoadState::Loaded {
+                return false;
+            }
+            match asset_store_inner.get(&entry.handle).byte_size() {
+                Some(bytes) => {
+                    unused_bytes += bytes;
+                    true
+                }
+                None => false,
+            }
+        });
+
+        if unused_bytes <= self.budget_bytes {
+            return;
+        }
+
+        let mut unused: Vec<(u64, usize)> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.is_unused())
+            .map(|(i, e)| (e.last_used_frame, i))
+            .collect();
+        unused.sort();
+
+        let mut to_evict = Vec::new();
+        for (_, i) in unused {
+            if unused_bytes <= self.budget_bytes {
+                break;
+            }
+            let handle = &self.entries[i].handle;
+            unused_bytes -= asset_store_inner.get(handle).byte_size().unwrap_or(0);
+            to_evict.push(i);
+        }
+
+        // Dropping the cache's handle lets the asset be cleaned up as usual.
+        to_evict.sort();
+        for i in to_evict.into_iter().rev() {
+            self.entries.swap_remove(i);
+        }
+    }
+}
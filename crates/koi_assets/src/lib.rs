@@ -1,4 +1,3 @@
-/// TODO: Paths aren't handled correctly yet.
 mod slotmap;
 use koi_ecs::Component;
 use koi_resources::Resources;
@@ -7,11 +6,42 @@ use slotmap::*;
 
 pub mod loader;
 
+mod hot_reload;
+pub use hot_reload::{watch_file, HotReload};
+
+mod load_state;
+pub use load_state::*;
+
+mod asset_event;
+pub use asset_event::*;
+
+mod keep_alive;
+pub use keep_alive::MemoryStats;
+
+mod load_group;
+pub use load_group::{LoadGroup, LoadGroupFuture, LoadProgress};
+
 pub struct AssetStoreInner<Asset: AssetTrait> {
     slot_map: SlotMap<Asset>,
-    path_to_slotmap: std::collections::HashMap<String, (WeakHandle<Asset>, Asset::Settings)>,
+    /// Assets loaded from each path. A path loaded with different settings has an entry for each.
+    path_to_slotmap:
+        std::collections::HashMap<String, Vec<(WeakHandle<Asset>, Asset::Settings)>>,
     drop_channel_sender: std::sync::mpsc::Sender<usize>,
     drop_channel_receiver: std::sync::mpsc::Receiver<usize>,
+    /// Events returned by [AssetStore::events], swapped with `pending_events` by
+    /// [AssetStore::finalize_asset_loads] so each event is seen exactly once.
+    events: Vec<AssetEvent<Asset>>,
+    /// Events since the most recent [AssetStore::finalize_asset_loads].
+    pending_events: Vec<AssetEvent<Asset>>,
+    /// Errors for assets that failed to load, keyed by their handle's index.
+    load_errors: std::collections::HashMap<usize, AssetError>,
+    error_placeholder: Option<Handle<Asset>>,
+    /// [LoadGroup]s waiting on assets that are still loading, keyed by their handle's index.
+    load_groups:
+        std::collections::HashMap<usize, Vec<std::sync::Arc<load_group::LoadGroupShared>>>,
+    /// Other files each path's assets were read from, reported with [watch_file].
+    #[cfg(not(target_arch = "wasm32"))]
+    watched_files: std::collections::HashMap<String, Vec<String>>,
 }
 
 impl<Asset: AssetTrait> AssetStoreInner<Asset> {
@@ -22,6 +52,13 @@ impl<Asset: AssetTrait> AssetStoreInner<Asset> {
             path_to_slotmap: std::collections::HashMap::new(),
             drop_channel_receiver,
             drop_channel_sender,
+            events: Vec::new(),
+            pending_events: Vec::new(),
+            load_errors: std::collections::HashMap::new(),
+            error_placeholder: None,
+            load_groups: std::collections::HashMap::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            watched_files: std::collections::HashMap::new(),
         }
     }
 
@@ -31,11 +68,36 @@ impl<Asset: AssetTrait> AssetStoreInner<Asset> {
             .try_iter()
             .filter_map(|indirection_index| {
                 let slot_map_handle = SlotMapHandle::from_index(indirection_index);
+                self.load_errors.remove(&indirection_index);
+                // Assets dropped before they finish loading will never load.
+                for load_group in self
+                    .load_groups
+                    .remove(&indirection_index)
+                    .unwrap_or_default()
+                {
+                    load_group.finish(true);
+                }
+                self.pending_events.push(AssetEvent::Dropped(WeakHandle {
+                    inner_handle: Handle {
+                        slot_map_handle: slot_map_handle.clone(),
+                        drop_handle: None,
+                        phantom: std::marker::PhantomData,
+                    },
+                    drop_handle: Some(std::sync::Weak::new()),
+                }));
                 if !self.slot_map.handle_is_placeholder(&slot_map_handle) {
                     let (asset, path) = self.slot_map.remove(slot_map_handle);
 
                     if let Some(path) = path {
-                        self.path_to_slotmap.remove(&path);
+                        if let Some(loaded) = self.path_to_slotmap.get_mut(&path) {
+                            loaded.retain(|(weak_handle, _)| {
+                                weak_handle.inner_handle.slot_map_handle.index()
+                                    != indirection_index
+                            });
+                            if loaded.is_empty() {
+                                self.path_to_slotmap.remove(&path);
+                            }
+                        }
                     }
                     Some(asset)
                 } else {
@@ -79,15 +141,24 @@ impl<Asset: AssetTrait> AssetStoreInner<Asset> {
     // }
 
     pub fn get(&self, handle: &Handle<Asset>) -> &Asset {
+        if !self.load_errors.is_empty() {
+            if let Some(error_placeholder) = &self.error_placeholder {
+                if self.load_errors.contains_key(&handle.slot_map_handle.index()) {
+                    if let Some(asset) = self.slot_map.get(&error_placeholder.slot_map_handle) {
+                        return asset;
+                    }
+                }
+            }
+        }
+
         let result = self.slot_map.get(&handle.slot_map_handle);
         if result.is_none() {
             println!("HANDLE0: {:?}", handle);
 
-            for p in self.path_to_slotmap.iter() {
-                println!(
-                    "PATH TO SLOTMAP: {:?}",
-                    (p.0, p.1 .0.upgrade().unwrap().slot_map_handle.index())
-                );
+            for (path, loaded) in self.path_to_slotmap.iter() {
+                for (weak_handle, _) in loaded {
+                    println!("PATH TO SLOTMAP: {:?}", (path, weak_handle));
+                }
             }
         }
         result.unwrap()
@@ -98,11 +169,56 @@ impl<Asset: AssetTrait> AssetStoreInner<Asset> {
     }
 
     pub fn replace(&mut self, handle: &Handle<Asset>, asset: Asset) {
+        self.load_errors.remove(&handle.slot_map_handle.index());
+        self.finish_load_groups(handle, false);
         if self.slot_map.handle_is_placeholder(&handle.slot_map_handle) {
             self.slot_map
                 .replace_placeholder(&handle.slot_map_handle, asset);
+            self.pending_events.push(AssetEvent::Loaded(handle.to_weak()));
         } else {
             *self.get_mut(handle) = asset;
+            self.pending_events.push(AssetEvent::Reloaded(handle.to_weak()));
+        }
+    }
+
+    /// Marks an asset as failed to load.
+    /// If the asset previously loaded successfully it keeps its previous value.
+    pub fn set_load_error(&mut self, handle: &Handle<Asset>, error: AssetError) {
+        if self.slot_map.handle_is_placeholder(&handle.slot_map_handle) {
+            self.load_errors
+                .insert(handle.slot_map_handle.index(), error.clone());
+        }
+        self.pending_events
+            .push(AssetEvent::Failed(handle.to_weak(), error));
+        self.finish_load_groups(handle, true);
+    }
+
+    fn finish_load_groups(&mut self, handle: &Handle<Asset>, failed: bool) {
+        if let Some(load_groups) = self.load_groups.remove(&handle.slot_map_handle.index()) {
+            for load_group in load_groups {
+                load_group.finish(failed);
+            }
+        }
+    }
+
+    /// Remembers the other files the assets at `path` were read from, for hot reloading.
+    #[cfg_attr(target_arch = "wasm32", allow(unused_variables))]
+    fn set_watched_files(&mut self, path: &str, watched_files: Vec<String>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if watched_files.is_empty() {
+            self.watched_files.remove(path);
+        } else {
+            self.watched_files.insert(path.into(), watched_files);
+        }
+    }
+
+    pub fn load_state(&self, handle: &Handle<Asset>) -> LoadState {
+        if let Some(error) = self.load_errors.get(&handle.slot_map_handle.index()) {
+            LoadState::Failed(error.clone())
+        } else if self.slot_map.handle_is_placeholder(&handle.slot_map_handle) {
+            LoadState::Loading
+        } else {
+            LoadState::Loaded
         }
     }
 }
@@ -110,6 +226,9 @@ impl<Asset: AssetTrait> AssetStoreInner<Asset> {
 pub struct AssetStore<Asset: AssetTrait> {
     asset_store_inner: AssetStoreInner<Asset>,
     loader: Box<dyn AssetLoaderTrait<Asset>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    file_watcher: hot_reload::FileWatcher,
+    keep_alive: Option<keep_alive::KeepAliveCache<Asset>>,
 }
 
 impl<Asset: AssetTrait> AssetStore<Asset> {
@@ -117,20 +236,30 @@ impl<Asset: AssetTrait> AssetStore<Asset> {
         Self {
             asset_store_inner: AssetStoreInner::new(placeholder),
             loader: Box::new(crate::loader::DoNothingLoader),
+            #[cfg(not(target_arch = "wasm32"))]
+            file_watcher: hot_reload::FileWatcher::new(),
+            keep_alive: None,
         }
     }
 
     pub fn new_with_load_functions<
         LoadResult: Send + 'static,
-        F: std::future::Future<Output = Option<LoadResult>> + Send + 'static,
+        F: std::future::Future<Output = Result<LoadResult, AssetErrorKind>> + Send + 'static,
     >(
         placeholder: Asset,
         load_task: fn(String, Asset::Settings) -> F,
-        handle_result: fn(LoadResult, Asset::Settings, &koi_resources::Resources) -> Option<Asset>,
+        handle_result: fn(
+            LoadResult,
+            Asset::Settings,
+            &koi_resources::Resources,
+        ) -> Result<Asset, AssetErrorKind>,
     ) -> Self {
         Self {
             asset_store_inner: AssetStoreInner::new(placeholder),
             loader: Box::new(crate::loader::Loader::new(load_task, handle_result)),
+            #[cfg(not(target_arch = "wasm32"))]
+            file_watcher: hot_reload::FileWatcher::new(),
+            keep_alive: None,
         }
     }
 
@@ -176,46 +305,249 @@ impl<Asset: AssetTrait> AssetStore<Asset> {
         self.asset_store_inner.replace(handle, asset)
     }
 
+    /// Assets that fail to load will use this asset instead of the placeholder.
+    /// For example a bright magenta texture that makes missing textures obvious.
+    pub fn set_error_placeholder(&mut self, handle: Handle<Asset>) {
+        self.asset_store_inner.error_placeholder = Some(handle);
+    }
+
+    /// Whether the asset is still loading, has loaded, or failed to load.
+    pub fn load_state(&self, handle: &Handle<Asset>) -> LoadState {
+        self.asset_store_inner.load_state(handle)
+    }
+
+    /// Finishes loads on the main thread.
+    /// If the [HotReload] resource exists this also reloads assets whose files changed.
     pub fn finalize_asset_loads(&mut self, resources: &Resources) {
         let AssetStore {
             asset_store_inner,
             loader,
+            ..
         } = self;
 
         loader.finalize_load_on_main_thread(resources, asset_store_inner);
+
+        if let Some(keep_alive) = &mut self.keep_alive {
+            keep_alive.evict(&self.asset_store_inner);
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(hot_reload) = resources.try_get::<HotReload>() {
+            if self.file_watcher.should_poll(&hot_reload) {
+                self.reload_changed_files();
+            }
+        }
+
+        let AssetStoreInner {
+            events,
+            pending_events,
+            ..
+        } = &mut self.asset_store_inner;
+        std::mem::swap(events, pending_events);
+        pending_events.clear();
     }
 
-    pub fn load(&mut self, path: &str, settings: Asset::Settings) -> Handle<Asset> {
-        if let Some(handle) = self
-            .asset_store_inner
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload_changed_files(&mut self) {
+        let AssetStore {
+            asset_store_inner,
+            loader,
+            file_watcher,
+        } = self;
+
+        for (path, loaded) in &asset_store_inner.path_to_slotmap {
+            // Every file is checked so all their modification times stay current.
+            let mut changed = file_watcher.file_changed(path);
+            for file in asset_store_inner.watched_files.get(path).into_iter().flatten() {
+                changed |= file_watcher.file_changed(file);
+            }
+            if changed {
+                for (weak_handle, settings) in loaded {
+                    if let Some(handle) = weak_handle.upgrade() {
+                        loader.load(path.clone(), settings.clone(), handle);
+                    }
+                }
+            }
+        }
+        file_watcher.remove_unchecked();
+        asset_store_inner
+            .watched_files
+            .retain(|path, _| asset_store_inner.path_to_slotmap.contains_key(path));
+    }
+
+    /// Events from before the most recent call to [AssetStore::finalize_asset_loads],
+    /// back to the call before it. This includes loads finished by that call.
+    ///
+    /// Each event is returned until the next call, so read these once per frame.
+    pub fn events(&self) -> impl Iterator<Item = &AssetEvent<Asset>> + '_ {
+        self.asset_store_inner.events.iter()
+    }
+
+    /// Handles to assets that were reloaded, from the same events as [AssetStore::events].
+    ///
+    /// Use this to refresh data derived from an asset.
+    pub fn reloaded(&self) -> impl Iterator<Item = Handle<Asset>> + '_ {
+        self.events().filter_map(|event| match event {
+            AssetEvent::Reloaded(weak_handle) => weak_handle.upgrade(),
+            _ => None,
+        })
+    }
+
+    /// Returns the asset already loaded from `path` with `settings`, if there is one.
+    pub fn get_by_path(&self, path: &str, settings: &Asset::Settings) -> Option<Handle<Asset>> {
+        self.asset_store_inner
             .path_to_slotmap
-            .get(path)
-            .and_then(|weak_handle| weak_handle.0.upgrade())
-        {
+            .get(&koi_fetch::normalize_path(path))?
+            .iter()
+            .find(|(_, s)| s == settings)
+            .and_then(|(weak_handle, _)| weak_handle.upgrade())
+    }
+
+    /// Loads an asset from a path, or returns the existing handle if it's already loaded
+    /// with the same settings.
+    ///
+    /// Paths are normalized first, so "./a.png" and "a.png" load the same asset.
+    /// See [koi_fetch::mount] for where paths are read from.
+    pub fn load(&mut self, path: &str, settings: Asset::Settings) -> Handle<Asset> {
+        let path = &koi_fetch::normalize_path(path);
+        if let Some(handle) = self.get_by_path(path, &settings) {
+            self.track_handle(&handle);
             handle
         } else {
-            let slot_map_handle = self
-                .asset_store_inner
-                .slot_map
-                .new_handle_pointing_at_placeholder(Some(path.into()));
-            let handle = self.asset_store_inner.new_handle(slot_map_handle);
-            self.asset_store_inner
-                .path_to_slotmap
-                .insert(path.into(), (handle.to_weak(), settings.clone()));
+            let handle = self.new_path_handle(path, &settings);
             self.loader.load(path.into(), settings, handle.clone());
             println!("NEW HANDLE: {:?}", handle);
             handle
         }
     }
 
+    /// Like [AssetStore::load] but returns a future that completes once the asset has been
+    /// finalized by [AssetStore::finalize_asset_loads].
+    ///
+    /// The future also completes if the asset fails to load. Check [AssetStore::load_state].
+    pub fn load_async(
+        &mut self,
+        path: &str,
+        settings: Asset::Settings,
+    ) -> impl std::future::Future<Output = Handle<Asset>> + Send + 'static {
+        let handle = self.load(path, settings);
+        let mut load_group = LoadGroup::new();
+        load_group.add(self, &handle);
+        let wait = load_group.wait();
+        async move {
+            wait.await;
+            handle
+        }
+    }
+
+    /// Loads and finalizes an asset on the current thread before returning.
+    ///
+    /// Intended for tools and tests. Assets this asset loads while finalizing,
+    /// like a glTF's textures, still load in the background.
+    /// A background load of the same asset that's already in progress is ignored.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_now(
+        &mut self,
+        path: &str,
+        settings: Asset::Settings,
+        resources: &Resources,
+    ) -> Handle<Asset> {
+        let path = &koi_fetch::normalize_path(path);
+        let handle = match self.get_by_path(path, &settings) {
+            Some(handle) => {
+                self.track_handle(&handle);
+                if self.load_state(&handle) == LoadState::Loaded {
+                    return handle;
+                }
+                handle
+            }
+            None => self.new_path_handle(path, &settings),
+        };
+        self.loader.load_now(
+            path.into(),
+            settings,
+            handle.clone(),
+            resources,
+            &mut self.asset_store_inner,
+        );
+        handle
+    }
+
+    /// Creates a handle for an asset that will be loaded from `path`.
+    fn new_path_handle(&mut self, path: &str, settings: &Asset::Settings) -> Handle<Asset> {
+        let slot_map_handle = self
+            .asset_store_inner
+            .slot_map
+            .new_handle_pointing_at_placeholder(Some(path.into()));
+        let handle = self.asset_store_inner.new_handle(slot_map_handle);
+        let loaded = self
+            .asset_store_inner
+            .path_to_slotmap
+            .entry(path.into())
+            .or_default();
+        // Forget assets that were dropped before they finished loading.
+        loaded.retain(|(weak_handle, _)| weak_handle.upgrade().is_some());
+        loaded.push((handle.to_weak(), settings.clone()));
+        self.track_handle(&handle);
+        handle
+    }
+
+    fn track_handle(&mut self, handle: &Handle<Asset>) {
+        load_group::track_dependency(&mut self.asset_store_inner, handle);
+        if let Some(keep_alive) = &mut self.keep_alive {
+            keep_alive.keep_alive(handle);
+        }
+    }
+
+    /// Keeps path-loaded assets alive after their last [Handle] is dropped,
+    /// so loading them again is instant.
+    ///
+    /// When the unused assets total more than `budget_bytes` the least recently used are
+    /// released. Assets that don't report an [AssetTrait::byte_size] aren't kept.
+    /// Pass `None` to disable, which releases all unused assets.
+    pub fn set_cache_budget(&mut self, budget_bytes: Option<usize>) {
+        match (budget_bytes, &mut self.keep_alive) {
+            (Some(budget_bytes), Some(keep_alive)) => keep_alive.budget_bytes = budget_bytes,
+            (Some(budget_bytes), None) => {
+                self.keep_alive = Some(keep_alive::KeepAliveCache::new(budget_bytes))
+            }
+            (None, _) => self.keep_alive = None,
+        }
+    }
+
+    pub fn memory_stats(&self) -> MemoryStats {
+        keep_alive::memory_stats(&self.asset_store_inner, self.keep_alive.as_ref())
+    }
+
     /// Reloads all assets that were loaded from a path.
     pub fn reload(&mut self) {
-        for (path, weak_handle) in &self.asset_store_inner.path_to_slotmap {
-            if let Some(handle) = weak_handle.0.upgrade() {
-                self.loader
-                    .load(path.into(), weak_handle.1.clone(), handle.clone());
+        for (path, loaded) in &self.asset_store_inner.path_to_slotmap {
+            for (weak_handle, settings) in loaded {
+                if let Some(handle) = weak_handle.upgrade() {
+                    self.loader.load(path.into(), settings.clone(), handle);
+                }
+            }
+        }
+    }
+
+    /// Reloads the assets loaded from `path`, with any settings.
+    /// Returns `false` if no asset is currently loaded from `path`.
+    pub fn reload_path(&mut self, path: &str) -> bool {
+        let path = &koi_fetch::normalize_path(path);
+        let mut reloaded = false;
+        for (weak_handle, settings) in self
+            .asset_store_inner
+            .path_to_slotmap
+            .get(path)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(handle) = weak_handle.upgrade() {
+                self.loader.load(path.into(), settings.clone(), handle);
+                reloaded = true;
             }
         }
+        reloaded
     }
 
     /// How many assets are currently loading.
@@ -223,7 +555,7 @@ impl<Asset: AssetTrait> AssetStore<Asset> {
         self.loader.currently_loading()
     }
 
-    pub fn is_placeholder(&mut self, handle: &Handle<Asset>) -> bool {
+    pub fn is_placeholder(&self, handle: &Handle<Asset>) -> bool {
         self.asset_store_inner
             .slot_map
             .handle_is_placeholder(&handle.slot_map_handle)
@@ -262,14 +594,15 @@ impl<T> Handle<T> {
         }
     }
 
-    fn to_weak(&self) -> WeakHandle<T> {
+    /// Returns a [WeakHandle] that doesn't keep the asset alive.
+    pub fn to_weak(&self) -> WeakHandle<T> {
         WeakHandle {
             inner_handle: Handle {
                 slot_map_handle: self.slot_map_handle.clone(),
                 drop_handle: None,
                 phantom: std::marker::PhantomData,
             },
-            drop_handle: std::sync::Arc::downgrade(self.drop_handle.as_ref().unwrap()),
+            drop_handle: self.drop_handle.as_ref().map(std::sync::Arc::downgrade),
         }
     }
 
@@ -321,7 +654,14 @@ impl Drop for DropHandle {
 }
 
 pub trait AssetTrait: Sized + 'static {
-    type Settings: Clone + Send;
+    /// Assets loaded from the same path with settings that aren't equal are loaded separately.
+    type Settings: Clone + Send + PartialEq;
+
+    /// Roughly how much memory the asset uses, or `None` if unknown.
+    /// Used by [AssetStore::memory_stats] and [AssetStore::set_cache_budget].
+    fn byte_size(&self) -> Option<usize> {
+        None
+    }
 }
 
 pub struct SyncGuard<T> {
@@ -340,9 +680,11 @@ impl<T> SyncGuard<T> {
 /// Nobody in the Rust Gamedev Discord yelled at me about this.
 unsafe impl<T> Sync for SyncGuard<T> {}
 
-struct WeakHandle<Asset: 'static> {
+/// A reference to an asset that doesn't keep it alive.
+pub struct WeakHandle<Asset: 'static> {
     inner_handle: Handle<Asset>,
-    drop_handle: std::sync::Weak<DropHandle>,
+    /// `None` for handles that are never dropped, like asset constants.
+    drop_handle: Option<std::sync::Weak<DropHandle>>,
 }
 
 impl<Asset> WeakHandle<Asset> {
@@ -350,7 +692,148 @@ impl<Asset> WeakHandle<Asset> {
     /// This will return [None] if all [Handle<T>]s have already been dropped.
     pub fn upgrade(&self) -> Option<Handle<Asset>> {
         let mut handle = self.inner_handle.clone();
-        handle.drop_handle = Some(self.drop_handle.upgrade()?);
+        if let Some(drop_handle) = &self.drop_handle {
+            handle.drop_handle = Some(drop_handle.upgrade()?);
+        }
         Some(handle)
     }
+
+    /// Returns `true` if every [Handle<T>] has been dropped.
+    pub fn is_dropped(&self) -> bool {
+        self.drop_handle
+            .as_ref()
+            .is_some_and(|drop_handle| drop_handle.strong_count() == 0)
+    }
+}
+
+impl<Asset> Clone for WeakHandle<Asset> {
+    fn clone(&self) -> Self {
+        Self {
+            inner_handle: self.inner_handle.clone(),
+            drop_handle: self.drop_handle.clone(),
+        }
+    }
+}
+
+impl<Asset> core::fmt::Debug for WeakHandle<Asset> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WeakHandle")
+            .field("index", &self.inner_handle.slot_map_handle.index())
+            .finish()
+    }
+}
+
+/// Compares which asset slot is referred to.
+/// The slot of a dropped asset may be reused by a later asset.
+impl<Asset> PartialEq<Handle<Asset>> for WeakHandle<Asset> {
+    fn eq(&self, other: &Handle<Asset>) -> bool {
+        self.inner_handle == *other
+    }
+}
+
+#[cfg(test)]
+struct TestAsset(usize);
+
+#[cfg(test)]
+impl AssetTrait for TestAsset {
+    type Settings = ();
+
+    fn byte_size(&self) -> Option<usize> {
+        Some(self.0)
+    }
+}
+
+#[cfg(test)]
+async fn load_test_asset(path: String, _settings: ()) -> Result<usize, AssetErrorKind> {
+    Ok(path.len())
+}
+
+#[cfg(test)]
+fn new_test_store() -> AssetStore<TestAsset> {
+    AssetStore::new_with_load_functions(TestAsset(0), load_test_asset, |length, _, _| {
+        Ok(TestAsset(length))
+    })
+}
+
+/// How many Loaded, Reloaded and Dropped events there are.
+#[cfg(test)]
+fn count_events(store: &AssetStore<TestAsset>) -> (usize, usize, usize) {
+    let mut counts = (0, 0, 0);
+    for event in store.events() {
+        match event {
+            AssetEvent::Loaded(_) => counts.0 += 1,
+            AssetEvent::Reloaded(_) => counts.1 += 1,
+            AssetEvent::Dropped(_) => counts.2 += 1,
+            AssetEvent::Failed(..) => {}
+        }
+    }
+    counts
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn asset_events_seen_once_test() {
+    let resources = Resources::new();
+    let mut store = new_test_store();
+    let handle = store.load_now("a.txt", (), &resources);
+    store.finalize_asset_loads(&resources);
+    assert_eq!(count_events(&store), (1, 0, 0));
+
+    // Events from outside of `finalize_asset_loads` aren't lost.
+    store.replace(&handle, TestAsset(3));
+    store.finalize_asset_loads(&resources);
+    assert_eq!(count_events(&store), (0, 1, 0));
+
+    drop(handle);
+    store.cleanup_dropped_assets();
+    store.finalize_asset_loads(&resources);
+    assert_eq!(count_events(&store), (0, 0, 1));
+
+    store.finalize_asset_loads(&resources);
+    assert_eq!(count_events(&store), (0, 0, 0));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn cache_budget_reuses_dropped_asset_test() {
+    let resources = Resources::new();
+    let mut store = new_test_store();
+    store.set_cache_budget(Some(100));
+
+    let handle = store.load_now("a.txt", (), &resources);
+    let index = handle.slot_map_handle.index();
+    drop(handle);
+    store.finalize_asset_loads(&resources);
+    store.cleanup_dropped_assets();
+
+    // Within budget the dropped asset is kept and reused without loading again.
+    let handle = store.load("a.txt", ());
+    assert_eq!(handle.slot_map_handle.index(), index);
+    assert_eq!(store.load_state(&handle), LoadState::Loaded);
+    assert_eq!(store.currently_loading(), 0);
+
+    // Over budget it's released.
+    store.set_cache_budget(Some(1));
+    drop(handle);
+    store.finalize_asset_loads(&resources);
+    store.cleanup_dropped_assets();
+    assert!(store.get_by_path("a.txt", &()).is_none());
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn load_now_ignores_in_flight_load_test() {
+    let resources = Resources::new();
+    let mut store = new_test_store();
+
+    let handle = store.load("a.txt", ());
+    assert_eq!(store.load_now("a.txt", (), &resources), handle);
+    assert_eq!(store.currently_loading(), 0);
+
+    // Let the background load finish. Its result must not replace the asset again.
+    ktasks::run_tasks_unless_there_are_workers();
+    store.finalize_asset_loads(&resources);
+    assert_eq!(count_events(&store), (1, 0, 0));
+    store.finalize_asset_loads(&resources);
+    assert_eq!(count_events(&store), (0, 0, 0));
 }
@@ -0,0 +1,178 @@
+use crate::{AssetStore, AssetStoreInner, AssetTrait, Handle, LoadState};
+use std::sync::{Arc, Mutex};
+
+/// How many of the assets in a [LoadGroup] have finished loading.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct LoadProgress {
+    pub loaded: usize,
+    pub failed: usize,
+    pub total: usize,
+}
+
+impl LoadProgress {
+    /// Returns `true` when every asset has either loaded or failed.
+    pub fn is_done(&self) -> bool {
+        self.loaded + self.failed == self.total
+    }
+
+    /// A value from 0.0 to 1.0 suitable for a loading bar.
+    pub fn fraction(&self) -> f32 {
+        if self.total == 0 {
+            1.0
+        } else {
+            (self.loaded + self.failed) as f32 / self.total as f32
+        }
+    }
+}
+
+#[derive(Default)]
+struct LoadGroupInner {
+    progress: LoadProgress,
+    /// The asset type and handle index of everything in the group, to avoid counting twice.
+    tracked: std::collections::HashSet<(std::any::TypeId, usize)>,
+    wakers: Vec<std::task::Waker>,
+}
+
+#[derive(Default)]
+pub(crate) struct LoadGroupShared {
+    inner: Mutex<LoadGroupInner>,
+}
+
+impl LoadGroupShared {
+    pub(crate) fn finish(&self, failed: bool) {
+        let mut inner = self.inner.lock().unwrap();
+        if failed {
+            inner.progress.failed += 1;
+        } else {
+            inner.progress.loaded += 1;
+        }
+        if inner.progress.is_done() {
+            for waker in inner.wakers.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Tracks the loading of a group of assets of any type.
+///
+/// Useful for loading screens that wait for everything a level needs.
+/// Assets loaded while finalizing an asset in the group, like the textures a glTF
+/// prefab references, are automatically added to the group as well.
+#[derive(Default)]
+pub struct LoadGroup {
+    shared: Arc<LoadGroupShared>,
+    /// Keeps the assets alive until the group is dropped.
+    handles: Vec<Box<dyn std::any::Any + Send + Sync>>,
+}
+
+impl LoadGroup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add<Asset: AssetTrait>(
+        &mut self,
+        asset_store: &mut AssetStore<Asset>,
+        handle: &Handle<Asset>,
+    ) {
+        track(&mut asset_store.asset_store_inner, &self.shared, handle);
+        self.handles.push(Box::new(handle.clone()));
+    }
+
+    pub fn progress(&self) -> LoadProgress {
+        self.shared.inner.lock().unwrap().progress
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.progress().is_done()
+    }
+
+    /// Returns a future that completes when every asset in the group has loaded or failed.
+    ///
+    /// Loads only progress when their [AssetStore] is finalized on the main thread,
+    /// so this should be awaited from a task that doesn't block the main thread.
+    pub fn wait(&self) -> LoadGroupFuture {
+        LoadGroupFuture {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+pub struct LoadGroupFuture {
+    shared: Arc<LoadGroupShared>,
+}
+
+impl std::future::Future for LoadGroupFuture {
+    type Output = LoadProgress;
+
+    fn poll(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        let mut inner = self.shared.inner.lock().unwrap();
+        if inner.progress.is_done() {
+            std::task::Poll::Ready(inner.progress)
+        } else {
+            inner.wakers.push(cx.waker().clone());
+            std::task::Poll::Pending
+        }
+    }
+}
+
+fn track<Asset: AssetTrait>(
+    asset_store_inner: &mut AssetStoreInner<Asset>,
+    shared: &Arc<LoadGroupShared>,
+    handle: &Handle<Asset>,
+) {
+    let index = handle.slot_map_handle.index();
+    {
+        let mut inner = shared.inner.lock().unwrap();
+        if !inner
+            .tracked
+            .insert((std::any::TypeId::of::<Asset>(), index))
+        {
+            return;
+        }
+        inner.progress.total += 1;
+    }
+
+    match asset_store_inner.load_state(handle) {
+        LoadState::Loading => asset_store_inner
+            .load_groups
+            .entry(index)
+            .or_default()
+            .push(shared.clone()),
+        LoadState::Loaded => shared.finish(false),
+        LoadState::Failed(_) => shared.finish(true),
+    }
+}
+
+thread_local! {
+    /// The groups of the asset currently being finalized on this thread.
+    static CURRENT_LOAD_GROUPS: std::cell::RefCell<Vec<Arc<LoadGroupShared>>> =
+        std::cell::RefCell::new(Vec::new());
+}
+
+/// Runs `f` with loads started on this thread counted as dependencies of `load_groups`.
+pub(crate) fn with_dependency_tracking<R>(
+    load_groups: Vec<Arc<LoadGroupShared>>,
+    f: impl FnOnce() -> R,
+) -> R {
+    let previous = CURRENT_LOAD_GROUPS.with(|c| c.replace(load_groups));
+    let result = f();
+    CURRENT_LOAD_GROUPS.with(|c| c.replace(previous));
+    result
+}
+
+/// Adds a newly requested asset to the groups of the asset currently being finalized.
+pub(crate) fn track_dependency<Asset: AssetTrait>(
+    asset_store_inner: &mut AssetStoreInner<Asset>,
+    handle: &Handle<Asset>,
+) {
+    CURRENT_LOAD_GROUPS.with(|load_groups| {
+        for shared in load_groups.borrow().iter() {
+            track(asset_store_inner, shared, handle);
+        }
+    });
+}
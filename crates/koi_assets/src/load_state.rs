@@ -0,0 +1,49 @@
+/// Why an asset failed to load.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AssetErrorKind {
+    /// The asset's bytes couldn't be read.
+    Fetch(koi_fetch::FetchError),
+    /// The file extension or format isn't supported.
+    UnsupportedFormat(String),
+    /// The bytes were read but couldn't be decoded.
+    Decode(String),
+    /// The asset was decoded but couldn't be finalized, e.g. a shader that fails to compile.
+    Finalize(String),
+}
+
+impl std::fmt::Display for AssetErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fetch(reason) => write!(f, "could not fetch file: {reason}"),
+            Self::UnsupportedFormat(format) => write!(f, "unsupported format: {format}"),
+            Self::Decode(reason) => write!(f, "could not decode: {reason}"),
+            Self::Finalize(reason) => write!(f, "could not finalize: {reason}"),
+        }
+    }
+}
+
+/// An error that occurred while loading an asset from a path.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssetError {
+    pub path: String,
+    pub kind: AssetErrorKind,
+}
+
+impl std::fmt::Display for AssetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to load {}: {}", self.path, self.kind)
+    }
+}
+
+impl std::error::Error for AssetError {}
+
+/// Returned by [crate::AssetStore::load_state]
+#[derive(Clone, Debug, PartialEq)]
+pub enum LoadState {
+    /// The asset hasn't finished loading and the placeholder is used in its place.
+    Loading,
+    Loaded,
+    /// The asset failed to load.
+    /// The error placeholder (or regular placeholder if there isn't one) is used in its place.
+    Failed(AssetError),
+}
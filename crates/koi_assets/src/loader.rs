@@ -1,4 +1,4 @@
-use crate::{AssetStoreInner, AssetTrait};
+use crate::{AssetError, AssetErrorKind, AssetStoreInner, AssetTrait, WeakHandle};
 use koi_resources::Resources;
 
 pub trait AssetLoaderTrait<Asset: AssetTrait> {
@@ -9,32 +9,54 @@ pub trait AssetLoaderTrait<Asset: AssetTrait> {
     ) {
     }
     fn load(&mut self, _path: String, _settings: Asset::Settings, _handle: crate::Handle<Asset>) {}
+    /// Loads and finalizes on the current thread.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_now(
+        &mut self,
+        _path: String,
+        _settings: Asset::Settings,
+        _handle: crate::Handle<Asset>,
+        _resources: &koi_resources::Resources,
+        _asset_store_inner: &mut AssetStoreInner<Asset>,
+    ) {
+    }
     fn currently_loading(&self) -> usize {
         0
     }
 }
+/// The result is `None` if the load was cancelled.
+type LoadMessage<LoadResult, Asset> = (
+    u64,
+    String,
+    Option<Result<LoadResult, AssetErrorKind>>,
+    <Asset as AssetTrait>::Settings,
+    WeakHandle<Asset>,
+);
+
 /// Abstract away off-thread loading boilerplate.
 pub struct Loader<
     LoadResult,
     Asset: AssetTrait,
-    F: std::future::Future<Output = Option<LoadResult>> + Send,
+    F: std::future::Future<Output = Result<LoadResult, AssetErrorKind>> + Send,
 > {
     load_task: fn(String, Asset::Settings) -> F,
-    handle_result: fn(LoadResult, Asset::Settings, &Resources) -> Option<Asset>,
-    sender: std::sync::mpsc::Sender<(LoadResult, Asset::Settings, crate::Handle<Asset>)>,
-    receiver: std::sync::mpsc::Receiver<(LoadResult, Asset::Settings, crate::Handle<Asset>)>,
-    currently_loading: usize,
+    handle_result: fn(LoadResult, Asset::Settings, &Resources) -> Result<Asset, AssetErrorKind>,
+    sender: std::sync::mpsc::Sender<LoadMessage<LoadResult, Asset>>,
+    receiver: std::sync::mpsc::Receiver<LoadMessage<LoadResult, Asset>>,
+    next_load_id: u64,
+    /// Loads that haven't been finalized yet.
+    in_flight: std::collections::HashMap<u64, WeakHandle<Asset>>,
 }
 
 impl<
         LoadResult: 'static + Send,
         Asset: AssetTrait + 'static,
-        F: std::future::Future<Output = Option<LoadResult>> + Send + 'static,
+        F: std::future::Future<Output = Result<LoadResult, AssetErrorKind>> + Send + 'static,
     > Loader<LoadResult, Asset, F>
 {
     pub fn new(
         load_task: fn(String, Asset::Settings) -> F,
-        handle_result: fn(LoadResult, Asset::Settings, &Resources) -> Option<Asset>,
+        handle_result: fn(LoadResult, Asset::Settings, &Resources) -> Result<Asset, AssetErrorKind>,
     ) -> Self {
         let (sender, receiver) = std::sync::mpsc::channel();
         Self {
@@ -42,7 +64,8 @@ impl<
             handle_result,
             sender,
             receiver,
-            currently_loading: 0,
+            next_load_id: 0,
+            in_flight: std::collections::HashMap::new(),
         }
     }
 
@@ -51,17 +74,53 @@ impl<
         resources: &koi_resources::Resources,
         asset_store: &mut crate::AssetStoreInner<Asset>,
     ) {
-        while let Ok((load_result, settings, handle)) = self.receiver.try_recv() {
-            if let Some(asset) = (self.handle_result)(load_result, settings, resources) {
-                asset_store.replace(&handle, asset)
-            } else {
-                // TODO: Better diagnostic messages
-                println!(
-                    "Failed to load asset of type: {:?}",
+        while let Ok((load_id, path, load_result, settings, weak_handle)) =
+            self.receiver.try_recv()
+        {
+            // Loads superseded by `load_now` were already removed and are ignored.
+            if self.in_flight.remove(&load_id).is_none() {
+                continue;
+            }
+            let (Some(load_result), Some(handle)) = (load_result, weak_handle.upgrade()) else {
+                continue;
+            };
+            self.finish_load(path, load_result, settings, &handle, resources, asset_store);
+        }
+    }
+
+    fn finish_load(
+        &self,
+        path: String,
+        load_result: Result<LoadResult, AssetErrorKind>,
+        settings: Asset::Settings,
+        handle: &crate::Handle<Asset>,
+        resources: &koi_resources::Resources,
+        asset_store: &mut crate::AssetStoreInner<Asset>,
+    ) {
+        // Assets loaded while finalizing this one count towards the same `LoadGroup`s.
+        let load_groups = asset_store
+            .load_groups
+            .get(&handle.slot_map_handle.index())
+            .cloned()
+            .unwrap_or_default();
+        let handle_result = self.handle_result;
+        let (result, watched_files) = crate::hot_reload::with_watched_files(|| {
+            crate::load_group::with_dependency_tracking(load_groups, || {
+                load_result.and_then(|r| handle_result(r, settings, resources))
+            })
+        });
+        asset_store.set_watched_files(&path, watched_files);
+        match result {
+            Ok(asset) => asset_store.replace(handle, asset),
+            Err(kind) => {
+                let error = AssetError { path, kind };
+                klog::log!(
+                    "ERROR: {} (asset type: {})",
+                    error,
                     std::any::type_name::<Asset>()
                 );
+                asset_store.set_load_error(handle, error);
             }
-            self.currently_loading -= 1;
         }
     }
 
@@ -73,22 +132,58 @@ impl<
     ) {
         let load_task = self.load_task;
         let sender = self.sender.clone();
-        self.currently_loading += 1;
+        let load_id = self.next_load_id;
+        self.next_load_id += 1;
+
+        // The task only holds a `WeakHandle` so that dropping every `Handle` cancels the load.
+        let weak_handle = handle.to_weak();
+        self.in_flight.insert(load_id, weak_handle.clone());
 
         ktasks::spawn(async move {
-            let settings0 = settings.clone();
-            if let Some(result) = (load_task)(path, settings0).await {
-                sender.send((result, settings, handle)).unwrap();
+            let result = Cancellable {
+                future: Box::pin((load_task)(path.clone(), settings.clone())),
+                handle: weak_handle.clone(),
             }
+            .await;
+            let _ = sender.send((load_id, path, result, settings, weak_handle));
         })
         .run();
     }
+
+    /// Loads that haven't finished, excluding those whose handles were all dropped.
+    pub fn currently_loading(&self) -> usize {
+        self.in_flight
+            .values()
+            .filter(|weak_handle| !weak_handle.is_dropped())
+            .count()
+    }
+}
+
+/// Stops polling a load's future at its next await point once every handle to the asset is
+/// dropped.
+struct Cancellable<F, Asset: 'static> {
+    future: std::pin::Pin<Box<F>>,
+    handle: WeakHandle<Asset>,
+}
+
+impl<F: std::future::Future, Asset> std::future::Future for Cancellable<F, Asset> {
+    type Output = Option<F::Output>;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        if self.handle.is_dropped() {
+            return std::task::Poll::Ready(None);
+        }
+        self.future.as_mut().poll(cx).map(Some)
+    }
 }
 
 impl<
         LoadResult: Send + 'static,
         Asset: AssetTrait,
-        F: std::future::Future<Output = Option<LoadResult>> + Send + 'static,
+        F: std::future::Future<Output = Result<LoadResult, AssetErrorKind>> + Send + 'static,
     > AssetLoaderTrait<Asset> for Loader<LoadResult, Asset, F>
 {
     fn finalize_load_on_main_thread(
@@ -101,8 +196,51 @@ impl<
     fn load(&mut self, path: String, settings: Asset::Settings, handle: crate::Handle<Asset>) {
         self.begin_load(path, settings, handle)
     }
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_now(
+        &mut self,
+        path: String,
+        settings: Asset::Settings,
+        handle: crate::Handle<Asset>,
+        resources: &koi_resources::Resources,
+        asset_store_inner: &mut AssetStoreInner<Asset>,
+    ) {
+        // A background load finishing later would replace this result with a spurious reload.
+        self.in_flight
+            .retain(|_, weak_handle| *weak_handle != handle);
+        let load_result = block_on((self.load_task)(path.clone(), settings.clone()));
+        self.finish_load(
+            path,
+            load_result,
+            settings,
+            &handle,
+            resources,
+            asset_store_inner,
+        );
+    }
     fn currently_loading(&self) -> usize {
-        self.currently_loading
+        Loader::currently_loading(self)
+    }
+}
+
+/// Runs a future to completion on the current thread.
+#[cfg(not(target_arch = "wasm32"))]
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    struct ThreadWaker(std::thread::Thread);
+    impl std::task::Wake for ThreadWaker {
+        fn wake(self: std::sync::Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = std::task::Waker::from(std::sync::Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = std::task::Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        match future.as_mut().poll(&mut context) {
+            std::task::Poll::Ready(output) => return output,
+            std::task::Poll::Pending => std::thread::park(),
+        }
     }
 }
 
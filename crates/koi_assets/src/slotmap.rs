@@ -119,7 +119,7 @@ impl<T> SlotMap<T> {
         self.new_handle_with_index(item_index, path)
     }
 
-    pub fn handle_is_placeholder(&mut self, handle: &SlotMapHandle<T>) -> bool {
+    pub fn handle_is_placeholder(&self, handle: &SlotMapHandle<T>) -> bool {
         self.indirection_indices[handle.indirection_index].item_index == 0
     }
 
@@ -96,8 +96,12 @@ pub fn update_audio_sources(
             .control::<oddio::SpatialScene, _>();
         spatial_scene_control.set_listener_rotation(q.into());
 
+        // Inactive sources keep their queued sounds until they're re-activated.
         for (_, (transform, audio_source)) in world
-            .query::<(&koi_transform::GlobalTransform, &mut AudioSource)>()
+            .query::<koi_ecs::Without<
+                (&koi_transform::GlobalTransform, &mut AudioSource),
+                &koi_ecs::InactiveInHierarchy,
+            >>()
             .iter()
         {
             // First we calculate how much this AudioSource has moved relative to the listener
@@ -187,7 +191,8 @@ pub fn update_audio_sources(
                                 .push(OddioHandle::Spatial(oddio_handle));
                             false
                         } else {
-                            true
+                            // Give up on sounds that failed to load.
+                            !matches!(sounds.load_state(sound_handle), LoadState::Failed(_))
                         }
                     } else {
                         let source = produce_oddio_filter(None);
@@ -21,9 +21,19 @@ impl Sound {
     }
 
     pub fn from_file_bytes(bytes: &[u8], extension: Option<&str>, scale: f32) -> Option<Self> {
+        Self::try_from_file_bytes(bytes, extension, scale).ok()
+    }
+
+    pub fn try_from_file_bytes(
+        bytes: &[u8],
+        extension: Option<&str>,
+        scale: f32,
+    ) -> Result<Self, koi_assets::AssetErrorKind> {
         match extension {
             Some("wav") => {
-                let mut sound = kaudio::load_wav_from_bytes(bytes).unwrap();
+                let mut sound = kaudio::load_wav_from_bytes(bytes).map_err(|_| {
+                    koi_assets::AssetErrorKind::Decode("invalid WAV file".into())
+                })?;
 
                 // Apply scale
                 sound.data.iter_mut().for_each(|s| *s *= scale);
@@ -37,9 +47,12 @@ impl Sound {
                         .map(|d| d.iter().sum::<f32>() / channels)
                         .collect();
                 }
-                Some(Sound::new_from_iter(sound.data.into_iter()))
+                Ok(Sound::new_from_iter(sound.data.into_iter()))
             }
-            _ => panic!("Unsupported audio format"),
+            _ => Err(koi_assets::AssetErrorKind::UnsupportedFormat(format!(
+                "audio file extension {:?}",
+                extension
+            ))),
         }
     }
 }
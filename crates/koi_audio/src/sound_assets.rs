@@ -1,10 +1,15 @@
 use crate::*;
+use koi_assets::AssetErrorKind;
 
 impl koi_assets::AssetTrait for Sound {
     type Settings = SoundSettings;
+
+    fn byte_size(&self) -> Option<usize> {
+        Some(std::mem::size_of_val::<[f32]>(&self.frames))
+    }
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct SoundSettings {
     pub scale: f32,
 }
@@ -15,19 +20,19 @@ impl Default for SoundSettings {
 }
 
 pub fn initialize_sound_assets(resources: &mut koi_resources::Resources) {
-    async fn load(path: String, settings: SoundSettings) -> Option<Sound> {
+    async fn load(path: String, settings: SoundSettings) -> Result<Sound, AssetErrorKind> {
         let bytes = koi_fetch::fetch_bytes(&path)
             .await
-            .unwrap_or_else(|_| panic!("Failed to open file: {}", path));
+            .map_err(AssetErrorKind::Fetch)?;
         let extension = std::path::Path::new(&path)
             .extension()
             .and_then(std::ffi::OsStr::to_str);
 
-        Sound::from_file_bytes(&bytes, extension, settings.scale)
+        Sound::try_from_file_bytes(&bytes, extension, settings.scale)
     }
     resources.add(koi_assets::AssetStore::new_with_load_functions(
         Sound::new_from_slice(&[0.0]),
         load,
-        |sound, _settings, _resources| Some(sound),
+        |sound, _settings, _resources| Ok(sound),
     ));
 }
@@ -75,11 +75,14 @@ pub fn update_camera_controls(
       input: &Input,
     time: &Time,
     mut query: Query<(&mut CameraControls, &mut Camera, &mut Transform)>, */
-    let query = world.query_mut::<(
-        &mut CameraControls,
-        &mut Camera,
-        &mut koi_transform::Transform,
-    )>();
+    let query = world.query_mut::<koi_ecs::Without<
+        (
+            &mut CameraControls,
+            &mut Camera,
+            &mut koi_transform::Transform,
+        ),
+        &koi_ecs::InactiveInHierarchy,
+    >>();
     for (_, (controls, camera, transform)) in query.into_iter() {
         if !controls.enabled {
             continue;
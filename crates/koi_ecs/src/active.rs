@@ -0,0 +1,118 @@
+use crate::HierachyExtension;
+
+/// Set to `Active(false)` to deactivate an [hecs::Entity] and all of its descendants.
+///
+/// Deactivated entities are skipped by the renderer, audio, animation, and camera controls
+/// but keep all of their components so they can be re-activated later.
+///
+/// Prefer [ActiveExtension::set_active] which immediately updates the hierarchy.
+/// If this component is inserted directly the hierarchy is updated
+/// by the next call to [ActiveExtension::update_active_hierarchy].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Active(pub bool);
+
+impl Default for Active {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+impl crate::WorldClonableTrait for Active {
+    fn clone_with_context(&self, _entity_migrator: &crate::EntityMigrator) -> Self {
+        *self
+    }
+}
+
+/// Automatically added to any [hecs::Entity] that is inactive
+/// or has an inactive ancestor.
+///
+/// Query with `Without<Q, &InactiveInHierarchy>` to skip inactive entities.
+#[derive(Clone, Copy, Debug)]
+pub struct InactiveInHierarchy;
+
+impl crate::WorldClonableTrait for InactiveInHierarchy {
+    fn clone_with_context(&self, _entity_migrator: &crate::EntityMigrator) -> Self {
+        *self
+    }
+}
+
+pub trait ActiveExtension {
+    /// Activates or deactivates an [hecs::Entity] and its descendants.
+    fn set_active(&mut self, entity: hecs::Entity, active: bool) -> Result<(), hecs::NoSuchEntity>;
+
+    /// Returns `false` if this [hecs::Entity] or any of its ancestors are inactive.
+    fn is_active_in_hierarchy(&self, entity: hecs::Entity) -> bool;
+
+    /// Adds or removes [InactiveInHierarchy] so that it matches the [Active] components
+    /// and the current hierarchy.
+    ///
+    /// This only visits inactive entities and their descendants so it's cheap
+    /// when most of the [hecs::World] is active.
+    fn update_active_hierarchy(&mut self);
+}
+
+impl ActiveExtension for hecs::World {
+    fn set_active(&mut self, entity: hecs::Entity, active: bool) -> Result<(), hecs::NoSuchEntity> {
+        self.insert_one(entity, Active(active))?;
+        self.update_active_hierarchy();
+        Ok(())
+    }
+
+    fn is_active_in_hierarchy(&self, entity: hecs::Entity) -> bool {
+        let inactive = |e| self.get::<&Active>(e).is_ok_and(|a| !a.0);
+        !(inactive(entity) || self.iterate_ancestors(entity).any(inactive))
+    }
+
+    fn update_active_hierarchy(&mut self) {
+        let mut should_be_inactive = std::collections::HashSet::new();
+        let mut to_visit: Vec<hecs::Entity> = self
+            .query::<&Active>()
+            .iter()
+            .filter_map(|(entity, active)| (!active.0).then_some(entity))
+            .collect();
+
+        while let Some(entity) = to_visit.pop() {
+            if should_be_inactive.insert(entity) {
+                to_visit.extend(self.iterate_children(entity));
+            }
+        }
+
+        // Entities left in `should_be_inactive` after this loop still need the marker.
+        let to_activate: Vec<hecs::Entity> = self
+            .query::<hecs::With<(), &InactiveInHierarchy>>()
+            .iter()
+            .filter_map(|(entity, _)| (!should_be_inactive.remove(&entity)).then_some(entity))
+            .collect();
+
+        for entity in to_activate {
+            let _ = self.remove_one::<InactiveInHierarchy>(entity);
+        }
+        for entity in should_be_inactive {
+            let _ = self.insert_one(entity, InactiveInHierarchy);
+        }
+    }
+}
+
+#[test]
+fn active_hierarchy_test() {
+    let mut world = hecs::World::new();
+    let parent = world.spawn(());
+    let child = world.spawn(());
+    let grandchild = world.spawn(());
+    world.set_parent(parent, child).unwrap();
+    world.set_parent(child, grandchild).unwrap();
+
+    world.set_active(child, false).unwrap();
+    assert!(world.get::<&InactiveInHierarchy>(parent).is_err());
+    assert!(world.get::<&InactiveInHierarchy>(child).is_ok());
+    assert!(world.get::<&InactiveInHierarchy>(grandchild).is_ok());
+    assert!(!world.is_active_in_hierarchy(grandchild));
+
+    world.unparent(grandchild).unwrap();
+    world.update_active_hierarchy();
+    assert!(world.get::<&InactiveInHierarchy>(grandchild).is_err());
+
+    world.set_active(child, true).unwrap();
+    assert!(world.get::<&InactiveInHierarchy>(child).is_err());
+    assert!(world.is_active_in_hierarchy(child));
+}
@@ -1,8 +1,8 @@
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct Child {
-    parent: hecs::Entity,
-    next_sibling: hecs::Entity,
-    previous_sibling: hecs::Entity,
+    pub(crate) parent: hecs::Entity,
+    pub(crate) next_sibling: hecs::Entity,
+    pub(crate) previous_sibling: hecs::Entity,
 }
 impl Child {
     pub fn parent(&self) -> hecs::Entity {
@@ -20,9 +20,9 @@ impl crate::WorldClonableTrait for Child {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct Parent {
-    arbitrary_child: Option<hecs::Entity>,
+    pub(crate) arbitrary_child: Option<hecs::Entity>,
 }
 
 impl crate::WorldClonableTrait for Parent {
@@ -35,6 +35,9 @@ impl crate::WorldClonableTrait for Parent {
     }
 }
 
+const CORRUPTED_HIERARCHY: &str =
+    "The hierarchy is corrupted. Call `validate_hierarchy` to find the cause.";
+
 pub trait HierachyExtension {
     fn set_parent(
         &mut self,
@@ -69,8 +72,9 @@ impl HierachyExtension for hecs::World {
 
         if let Ok(parent) = self.get::<&Parent>(parent) {
             if let Some(arbitrary_child) = parent.arbitrary_child {
-                let mut arbitrary_child_component =
-                    self.get::<&mut Child>(arbitrary_child).unwrap();
+                let mut arbitrary_child_component = self
+                    .get::<&mut Child>(arbitrary_child)
+                    .expect(CORRUPTED_HIERARCHY);
                 next_sibling = arbitrary_child_component.next_sibling;
                 arbitrary_child_component.next_sibling = child;
                 previous_sibling = arbitrary_child;
@@ -123,8 +127,12 @@ impl HierachyExtension for hecs::World {
 
         // Connect siblings
         if let Some((previous, next)) = previous_and_next_sibling {
-            self.get::<&mut Child>(previous).unwrap().next_sibling = next;
-            self.get::<&mut Child>(next).unwrap().previous_sibling = previous;
+            self.get::<&mut Child>(previous)
+                .expect(CORRUPTED_HIERARCHY)
+                .next_sibling = next;
+            self.get::<&mut Child>(next)
+                .expect(CORRUPTED_HIERARCHY)
+                .previous_sibling = previous;
         }
 
         if let Err(hecs::ComponentError::NoSuchEntity) = self.remove_one::<Child>(child_entity) {
@@ -0,0 +1,311 @@
+use crate::{Child, HierachyExtension, Parent, WorldCloner};
+use hecs::Entity;
+use std::collections::{HashMap, HashSet};
+
+/// A human readable name for an [Entity], shown by [dump_hierarchy].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Name(pub String);
+
+impl crate::WorldClonableTrait for Name {
+    fn clone_with_context(&self, _entity_migrator: &crate::EntityMigrator) -> Self {
+        self.clone()
+    }
+}
+
+/// An inconsistency found by [validate_hierarchy].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HierarchyError {
+    /// A [Child]'s parent [Entity] no longer exists.
+    MissingParent { child: Entity, parent: Entity },
+    /// A [Child]'s parent doesn't have a [Parent] component.
+    ParentComponentMissing { child: Entity, parent: Entity },
+    /// A [Child]'s sibling doesn't exist or isn't a [Child].
+    MissingSibling { child: Entity, sibling: Entity },
+    /// A [Child]'s sibling has a different parent.
+    SiblingHasDifferentParent { child: Entity, sibling: Entity },
+    /// `child`'s next sibling doesn't point back to `child` as its previous sibling.
+    BrokenSiblingLink { child: Entity, next_sibling: Entity },
+    /// A [Parent] points at an [Entity] that isn't one of its children.
+    InvalidFirstChild { parent: Entity, child: Entity },
+    /// A [Child] can't be reached from its parent's list of children.
+    UnreachableChild { parent: Entity, child: Entity },
+    /// An [Entity] is its own ancestor.
+    Cycle { entity: Entity },
+}
+
+impl std::fmt::Display for HierarchyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingParent { child, parent } => {
+                write!(f, "{child:?} has parent {parent:?} which doesn't exist")
+            }
+            Self::ParentComponentMissing { child, parent } => write!(
+                f,
+                "{child:?} has parent {parent:?} which has no `Parent` component"
+            ),
+            Self::MissingSibling { child, sibling } => write!(
+                f,
+                "{child:?} has sibling {sibling:?} which doesn't exist or isn't a child"
+            ),
+            Self::SiblingHasDifferentParent { child, sibling } => write!(
+                f,
+                "{child:?} has sibling {sibling:?} which has a different parent"
+            ),
+            Self::BrokenSiblingLink {
+                child,
+                next_sibling,
+            } => write!(
+                f,
+                "{child:?} has next sibling {next_sibling:?} whose previous sibling isn't {child:?}"
+            ),
+            Self::InvalidFirstChild { parent, child } => write!(
+                f,
+                "{parent:?} points at {child:?} which isn't one of its children"
+            ),
+            Self::UnreachableChild { parent, child } => write!(
+                f,
+                "{child:?} can't be reached from the children of its parent {parent:?}"
+            ),
+            Self::Cycle { entity } => write!(f, "{entity:?} is its own ancestor"),
+        }
+    }
+}
+
+impl std::error::Error for HierarchyError {}
+
+/// Checks that all [Parent] and [Child] components are consistent with each other.
+///
+/// Returns every problem found instead of stopping at the first.
+pub fn validate_hierarchy(world: &hecs::World) -> Result<(), Vec<HierarchyError>> {
+    let mut errors = Vec::new();
+    let mut children_of_parent: HashMap<Entity, Vec<Entity>> = HashMap::new();
+
+    for (entity, child) in world.query::<&Child>().iter() {
+        children_of_parent
+            .entry(child.parent)
+            .or_default()
+            .push(entity);
+
+        if !world.contains(child.parent) {
+            errors.push(HierarchyError::MissingParent {
+                child: entity,
+                parent: child.parent,
+            });
+        } else if world.get::<&Parent>(child.parent).is_err() {
+            errors.push(HierarchyError::ParentComponentMissing {
+                child: entity,
+                parent: child.parent,
+            });
+        }
+
+        for sibling in [child.next_sibling, child.previous_sibling] {
+            match world.get::<&Child>(sibling) {
+                Err(_) => errors.push(HierarchyError::MissingSibling {
+                    child: entity,
+                    sibling,
+                }),
+                Ok(sibling_child) if sibling_child.parent != child.parent => {
+                    errors.push(HierarchyError::SiblingHasDifferentParent {
+                        child: entity,
+                        sibling,
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        if let Ok(next_sibling) = world.get::<&Child>(child.next_sibling) {
+            if next_sibling.previous_sibling != entity {
+                errors.push(HierarchyError::BrokenSiblingLink {
+                    child: entity,
+                    next_sibling: child.next_sibling,
+                });
+            }
+        }
+
+        // Entities below a cycle reach it without returning to themselves.
+        // Only the members of the cycle are reported.
+        let mut visited = HashSet::new();
+        let mut ancestor = child.parent;
+        loop {
+            if ancestor == entity {
+                errors.push(HierarchyError::Cycle { entity });
+                break;
+            }
+            if !visited.insert(ancestor) {
+                break;
+            }
+            match world.get::<&Child>(ancestor) {
+                Ok(c) => ancestor = c.parent,
+                Err(_) => break,
+            }
+        }
+    }
+
+    for (parent_entity, parent) in world.query::<&Parent>().iter() {
+        let mut reachable = HashSet::new();
+        if let Some(first_child) = parent.arbitrary_child {
+            let mut next = first_child;
+            loop {
+                match world.get::<&Child>(next) {
+                    Ok(child) if child.parent == parent_entity => {
+                        if !reachable.insert(next) || reachable.len() as u32 > world.len() {
+                            break;
+                        }
+                        next = child.next_sibling;
+                        if next == first_child {
+                            break;
+                        }
+                    }
+                    _ => {
+                        if next == first_child {
+                            errors.push(HierarchyError::InvalidFirstChild {
+                                parent: parent_entity,
+                                child: first_child,
+                            });
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+
+        for child in children_of_parent
+            .get(&parent_entity)
+            .into_iter()
+            .flatten()
+        {
+            if !reachable.contains(child) {
+                errors.push(HierarchyError::UnreachableChild {
+                    parent: parent_entity,
+                    child: *child,
+                });
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Returns an indented tree of every [Entity] with its [Name] and component types.
+///
+/// Component type names are only known for types registered with the [WorldCloner].
+/// Entities in a cycle, which have no root above them, are listed after the roots
+/// starting from a member of the cycle.
+pub fn dump_hierarchy(world: &hecs::World, world_cloner: &WorldCloner) -> String {
+    fn dump_entity(
+        world: &hecs::World,
+        world_cloner: &WorldCloner,
+        entity: Entity,
+        depth: usize,
+        listed: &mut HashSet<Entity>,
+        output: &mut String,
+    ) {
+        use std::fmt::Write;
+
+        let _ = write!(output, "{:indent$}{:?}", "", entity, indent = depth * 2);
+        // Stop on cycles. `validate_hierarchy` will report them.
+        if !listed.insert(entity) {
+            let _ = writeln!(output, " (already listed)");
+            return;
+        }
+        if let Ok(name) = world.get::<&Name>(entity) {
+            let _ = write!(output, " \"{}\"", name.0);
+        }
+
+        let mut type_names: Vec<String> = world
+            .entity(entity)
+            .map(|entity_ref| {
+                entity_ref
+                    .component_types()
+                    .map(|type_id| {
+                        world_cloner
+                            .type_name(type_id)
+                            .map_or_else(|| format!("{type_id:?}"), |n| n.to_string())
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        type_names.sort();
+        let _ = writeln!(output, " [{}]", type_names.join(", "));
+
+        for child in world.iterate_children(entity) {
+            dump_entity(world, world_cloner, child, depth + 1, listed, output);
+        }
+    }
+
+    let mut output = String::new();
+    let mut listed = HashSet::new();
+    for (entity, _) in world.query::<hecs::Without<(), &Child>>().iter() {
+        dump_entity(world, world_cloner, entity, 0, &mut listed, &mut output);
+    }
+
+    for (entity, _) in world.query::<&Child>().iter() {
+        if listed.contains(&entity) {
+            continue;
+        }
+        // Walk up until the walk repeats, which is a member of the cycle,
+        // or reaches a missing parent.
+        let mut top = entity;
+        let mut visited = HashSet::new();
+        while visited.insert(top) {
+            match world.get::<&Child>(top) {
+                Ok(child) if world.contains(child.parent) => top = child.parent,
+                _ => break,
+            }
+        }
+        dump_entity(world, world_cloner, top, 0, &mut listed, &mut output);
+    }
+    output
+}
+
+#[test]
+fn validate_hierarchy_test() {
+    let mut world = hecs::World::new();
+    let parent = world.spawn((Name("Parent".into()),));
+    let a = world.spawn(());
+    let b = world.spawn(());
+    world.set_parent(parent, a).unwrap();
+    world.set_parent(parent, b).unwrap();
+    let c = world.spawn(());
+    world.set_parent(b, c).unwrap();
+    assert_eq!(validate_hierarchy(&world), Ok(()));
+
+    world.get::<&mut Child>(a).unwrap().previous_sibling = a;
+    assert_eq!(
+        validate_hierarchy(&world),
+        Err(vec![HierarchyError::BrokenSiblingLink {
+            child: b,
+            next_sibling: a
+        }])
+    );
+
+    let dump = dump_hierarchy(&world, &WorldCloner::new());
+    assert!(dump.starts_with(&format!("{parent:?} \"Parent\"")));
+}
+
+#[test]
+fn hierarchy_cycle_test() {
+    let mut world = hecs::World::new();
+    let a = world.spawn(());
+    let b = world.spawn(());
+    let below = world.spawn(());
+    world.set_parent(a, b).unwrap();
+    world.set_parent(b, below).unwrap();
+    // `set_parent` doesn't prevent cycles.
+    world.set_parent(b, a).unwrap();
+
+    let mut errors = validate_hierarchy(&world).unwrap_err();
+    errors.retain(|error| matches!(error, HierarchyError::Cycle { .. }));
+    assert_eq!(errors.len(), 2);
+    assert!(!errors.contains(&HierarchyError::Cycle { entity: below }));
+
+    let dump = dump_hierarchy(&world, &WorldCloner::new());
+    for entity in [a, b, below] {
+        assert!(dump.contains(&format!("{entity:?} [")));
+    }
+}
@@ -1,4 +1,8 @@
+pub use active::*;
 pub use hierarchy::*;
+pub use hierarchy_debug::*;
+pub use snapshot::*;
+pub use undo_history::*;
 
 pub mod world_cloner;
 
@@ -6,7 +10,11 @@ pub use hecs::*;
 pub use koi_ecs_derive::*;
 pub use world_cloner::*;
 
+mod active;
 mod hierarchy;
+mod hierarchy_debug;
+mod snapshot;
+mod undo_history;
 
 pub struct World {
     hecs_world: hecs::World,
@@ -0,0 +1,293 @@
+use crate::{EntityMigrator, World, WorldClonableTrait, WorldCloner};
+use hecs::Entity;
+use std::any::Any;
+
+type BoxedComponent = Box<dyn Any + Send + Sync>;
+
+/// Type-erased operations used to diff and restore a registered component.
+#[derive(Clone, Copy)]
+pub(crate) struct SnapshotFunctions {
+    type_name: &'static str,
+    get_cloned: fn(&hecs::World, Entity) -> Option<BoxedComponent>,
+    clone_boxed: fn(&BoxedComponent) -> BoxedComponent,
+    insert: fn(&mut hecs::World, Entity, BoxedComponent),
+    remove: fn(&mut hecs::World, Entity),
+    equals: Option<fn(&hecs::World, Entity, &hecs::World, Entity) -> bool>,
+}
+
+impl SnapshotFunctions {
+    pub(crate) fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub(crate) fn new<T: WorldClonableTrait>(
+        equals: Option<fn(&hecs::World, Entity, &hecs::World, Entity) -> bool>,
+    ) -> Self {
+        Self {
+            type_name: std::any::type_name::<T>(),
+            get_cloned: |world, entity| {
+                let component = world.get::<&T>(entity).ok()?;
+                Some(Box::new(
+                    component.clone_with_context(&EntityMigrator::IDENTITY),
+                ))
+            },
+            clone_boxed: |component| {
+                Box::new(
+                    component
+                        .downcast_ref::<T>()
+                        .unwrap()
+                        .clone_with_context(&EntityMigrator::IDENTITY),
+                )
+            },
+            insert: |world, entity, component| {
+                let _ = world.insert_one(entity, *component.downcast::<T>().unwrap());
+            },
+            remove: |world, entity| {
+                let _ = world.remove_one::<T>(entity);
+            },
+            equals,
+        }
+    }
+}
+
+/// A copy of a [World] taken with [WorldCloner::snapshot].
+///
+/// The snapshot keeps the same [Entity] handles as the [World] it was taken from
+/// so two snapshots of the same [World] can be compared with [WorldCloner::diff].
+pub struct WorldSnapshot {
+    world: World,
+}
+
+impl WorldSnapshot {
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+}
+
+struct ComponentChange {
+    entity: Entity,
+    functions: SnapshotFunctions,
+    before: Option<BoxedComponent>,
+    after: Option<BoxedComponent>,
+}
+
+/// The differences between two [WorldSnapshot]s.
+///
+/// A [WorldDiff] can be applied to a [World] that matches the earlier snapshot
+/// to produce the later one, or reverted on a [World] that matches the later snapshot.
+///
+/// Components that aren't registered with the [WorldCloner] can't be recreated.
+/// Entities despawned by applying or reverting the diff are kept with it so they're
+/// respawned with those components, but entities despawned before the diff was taken
+/// are respawned with only registered components.
+#[derive(Default)]
+pub struct WorldDiff {
+    spawned: Vec<Entity>,
+    despawned: Vec<Entity>,
+    changes: Vec<ComponentChange>,
+    removed_entities: hecs::World,
+}
+
+impl WorldDiff {
+    pub fn is_empty(&self) -> bool {
+        self.spawned.is_empty() && self.despawned.is_empty() && self.changes.is_empty()
+    }
+
+    /// [Entity]s that exist in the later snapshot but not the earlier one.
+    pub fn spawned(&self) -> &[Entity] {
+        &self.spawned
+    }
+
+    /// [Entity]s that exist in the earlier snapshot but not the later one.
+    pub fn despawned(&self) -> &[Entity] {
+        &self.despawned
+    }
+
+    /// Every component that was added, removed, or changed, along with its type name.
+    pub fn changed_components(&self) -> impl Iterator<Item = (Entity, &'static str)> + '_ {
+        self.changes
+            .iter()
+            .map(|change| (change.entity, change.functions.type_name))
+    }
+
+    pub fn apply(&mut self, world: &mut World) {
+        self.apply_inner(world, false);
+    }
+
+    pub fn revert(&mut self, world: &mut World) {
+        self.apply_inner(world, true);
+    }
+
+    fn apply_inner(&mut self, world: &mut World, reverse: bool) {
+        let (to_despawn, to_spawn) = if reverse {
+            (&self.spawned, &self.despawned)
+        } else {
+            (&self.despawned, &self.spawned)
+        };
+
+        // `hecs_world` is used directly because `World::despawn` also despawns children,
+        // which are handled by their own entries in the diff.
+        for entity in to_despawn {
+            if let Ok(taken) = world.hecs_world.take(*entity) {
+                self.removed_entities.spawn_at(*entity, taken);
+            }
+        }
+        for entity in to_spawn {
+            // Respawning an existing entity would drop its components.
+            if world.hecs_world.contains(*entity) {
+                continue;
+            }
+            match self.removed_entities.take(*entity) {
+                Ok(taken) => world.hecs_world.spawn_at(*entity, taken),
+                Err(_) => world.hecs_world.spawn_at(*entity, ()),
+            }
+        }
+
+        for change in &self.changes {
+            let value = if reverse { &change.before } else { &change.after };
+            match value {
+                Some(value) => (change.functions.insert)(
+                    &mut world.hecs_world,
+                    change.entity,
+                    (change.functions.clone_boxed)(value),
+                ),
+                None => (change.functions.remove)(&mut world.hecs_world, change.entity),
+            }
+        }
+    }
+}
+
+impl WorldCloner {
+    /// Captures a [WorldSnapshot] of all registered components in `world`.
+    pub fn snapshot(&mut self, world: &mut World) -> WorldSnapshot {
+        let mut snapshot_world = World::new();
+        self.clone_world_preserving_entities(world, &mut snapshot_world);
+        WorldSnapshot {
+            world: snapshot_world,
+        }
+    }
+
+    /// Computes the [WorldDiff] that turns `before` into `after`.
+    ///
+    /// Changes to a component's value are only detected for components
+    /// registered with [WorldCloner::register_diffable_type].
+    pub fn diff(&self, before: &WorldSnapshot, after: &WorldSnapshot) -> WorldDiff {
+        let mut diff = WorldDiff::default();
+        let mut changes = Vec::new();
+
+        let mut push_change = |entity, type_id, include_before: bool, include_after: bool| {
+            if let Some(functions) = self.snapshot_functions(type_id) {
+                changes.push(ComponentChange {
+                    entity,
+                    functions: *functions,
+                    before: include_before
+                        .then(|| (functions.get_cloned)(&before.world, entity))
+                        .flatten(),
+                    after: include_after
+                        .then(|| (functions.get_cloned)(&after.world, entity))
+                        .flatten(),
+                });
+            }
+        };
+
+        for entity_ref in before.world.iter() {
+            let entity = entity_ref.entity();
+            if !after.world.contains(entity) {
+                diff.despawned.push(entity);
+                for type_id in entity_ref.component_types() {
+                    push_change(entity, type_id, true, false);
+                }
+            }
+        }
+
+        for entity_ref in after.world.iter() {
+            let entity = entity_ref.entity();
+            if let Ok(before_entity_ref) = before.world.entity(entity) {
+                for type_id in entity_ref.component_types() {
+                    if !before_entity_ref.component_types().any(|t| t == type_id) {
+                        push_change(entity, type_id, false, true);
+                    } else if let Some(equals) = self
+                        .snapshot_functions(type_id)
+                        .and_then(|functions| functions.equals)
+                    {
+                        if !equals(&before.world, entity, &after.world, entity) {
+                            push_change(entity, type_id, true, true);
+                        }
+                    }
+                }
+                for type_id in before_entity_ref.component_types() {
+                    if !entity_ref.component_types().any(|t| t == type_id) {
+                        push_change(entity, type_id, true, false);
+                    }
+                }
+            } else {
+                diff.spawned.push(entity);
+                for type_id in entity_ref.component_types() {
+                    push_change(entity, type_id, false, true);
+                }
+            }
+        }
+
+        diff.changes = changes;
+        diff
+    }
+
+    /// Returns `world` to the state captured in `snapshot`.
+    ///
+    /// The returned [WorldDiff] can be reverted to undo the restore.
+    pub fn restore_snapshot(&mut self, snapshot: &WorldSnapshot, world: &mut World) -> WorldDiff {
+        let current = self.snapshot(world);
+        let mut diff = self.diff(&current, snapshot);
+        diff.apply(world);
+        diff
+    }
+}
+
+#[test]
+fn snapshot_diff_test() {
+    use crate::HierachyExtension;
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct A(i32);
+
+    impl WorldClonableTrait for A {
+        fn clone_with_context(&self, _: &EntityMigrator) -> Self {
+            self.clone()
+        }
+    }
+
+    let mut world_cloner = WorldCloner::new();
+    world_cloner.register_diffable_type::<A>();
+    world_cloner.register_diffable_type::<crate::Child>();
+    world_cloner.register_diffable_type::<crate::Parent>();
+
+    /// Not registered, so only kept for entities the diff despawns.
+    struct B;
+
+    let mut world = World::new();
+    let a = world.spawn((A(0),));
+    let b = world.spawn((A(1), B));
+    let before = world_cloner.snapshot(&mut world);
+
+    world.get::<&mut A>(a).unwrap().0 = 5;
+    let c = world.spawn((A(2), B));
+    world.set_parent(a, c).unwrap();
+    world.despawn(b).unwrap();
+    let after = world_cloner.snapshot(&mut world);
+
+    let mut diff = world_cloner.diff(&before, &after);
+    assert_eq!(diff.spawned(), &[c]);
+    assert_eq!(diff.despawned(), &[b]);
+
+    diff.revert(&mut world);
+    assert_eq!(world.get::<&A>(a).unwrap().0, 0);
+    assert_eq!(world.get::<&A>(b).unwrap().0, 1);
+    assert!(!world.contains(c));
+    assert!(world.get::<&crate::Parent>(a).is_err());
+
+    diff.apply(&mut world);
+    assert_eq!(world.get::<&A>(a).unwrap().0, 5);
+    assert!(!world.contains(b));
+    assert_eq!(world.iterate_children(a).collect::<Vec<_>>(), vec![c]);
+    assert!(world.get::<&B>(c).is_ok());
+}
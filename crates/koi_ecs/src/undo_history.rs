@@ -0,0 +1,108 @@
+use crate::{World, WorldCloner, WorldDiff, WorldSnapshot};
+
+/// Records edits to a [World] as [WorldDiff]s so they can be undone and redone.
+///
+/// Wrap an edit in [UndoHistory::begin_edit] and [UndoHistory::end_edit],
+/// or use [UndoHistory::record].
+pub struct UndoHistory {
+    undo_stack: Vec<WorldDiff>,
+    redo_stack: Vec<WorldDiff>,
+    edit_start: Option<WorldSnapshot>,
+    /// The oldest edits are forgotten when there are more than this many.
+    pub max_steps: usize,
+}
+
+impl Default for UndoHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UndoHistory {
+    pub fn new() -> Self {
+        Self {
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            edit_start: None,
+            max_steps: 100,
+        }
+    }
+
+    pub fn begin_edit(&mut self, world_cloner: &mut WorldCloner, world: &mut World) {
+        self.edit_start = Some(world_cloner.snapshot(world));
+    }
+
+    /// Returns `true` if anything changed since [UndoHistory::begin_edit].
+    ///
+    /// Panics in debug builds if [UndoHistory::begin_edit] wasn't called first.
+    pub fn end_edit(&mut self, world_cloner: &mut WorldCloner, world: &mut World) -> bool {
+        debug_assert!(
+            self.edit_start.is_some(),
+            "`UndoHistory::end_edit` called without `begin_edit`"
+        );
+        let Some(edit_start) = self.edit_start.take() else {
+            return false;
+        };
+
+        let edit_end = world_cloner.snapshot(world);
+        let diff = world_cloner.diff(&edit_start, &edit_end);
+        if diff.is_empty() {
+            return false;
+        }
+
+        self.redo_stack.clear();
+        self.undo_stack.push(diff);
+        if self.undo_stack.len() > self.max_steps {
+            self.undo_stack.remove(0);
+        }
+        true
+    }
+
+    /// Runs `edit` and records the changes it makes.
+    pub fn record(
+        &mut self,
+        world_cloner: &mut WorldCloner,
+        world: &mut World,
+        edit: impl FnOnce(&mut World),
+    ) -> bool {
+        self.begin_edit(world_cloner, world);
+        edit(world);
+        self.end_edit(world_cloner, world)
+    }
+
+    /// Returns `false` if there was nothing to undo.
+    pub fn undo(&mut self, world: &mut World) -> bool {
+        if let Some(mut diff) = self.undo_stack.pop() {
+            diff.revert(world);
+            self.redo_stack.push(diff);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns `false` if there was nothing to redo.
+    pub fn redo(&mut self, world: &mut World) -> bool {
+        if let Some(mut diff) = self.redo_stack.pop() {
+            diff.apply(world);
+            self.undo_stack.push(diff);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.edit_start = None;
+    }
+}
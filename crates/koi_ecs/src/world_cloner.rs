@@ -1,4 +1,4 @@
-use crate::World;
+use crate::{snapshot::SnapshotFunctions, World};
 use hecs::*;
 use std::any::TypeId;
 
@@ -14,18 +14,29 @@ pub struct WorldCloner {
 struct RegisteredComponent {
     add_type: fn(&mut ColumnBatchType),
     clone_components: fn(&Archetype, &mut ColumnBatchBuilder, &EntityMigrator),
+    snapshot_functions: SnapshotFunctions,
 }
 
 pub struct EntityMigrator<'a> {
-    old_to_new_entities: &'a [Option<Entity>],
+    /// If `None` every [Entity] migrates to itself.
+    old_to_new_entities: Option<&'a [Option<Entity>]>,
 }
 
 impl<'a> EntityMigrator<'a> {
+    /// Migrates every [Entity] to itself.
+    /// Used when cloning components that stay in the same [World].
+    pub const IDENTITY: EntityMigrator<'static> = EntityMigrator {
+        old_to_new_entities: None,
+    };
+
     pub fn migrate(&self, old_entity: Entity) -> Option<Entity> {
-        self.old_to_new_entities
-            .get(old_entity.id() as usize)
-            .cloned()
-            .flatten()
+        match self.old_to_new_entities {
+            Some(old_to_new_entities) => old_to_new_entities
+                .get(old_entity.id() as usize)
+                .cloned()
+                .flatten(),
+            None => Some(old_entity),
+        }
     }
 }
 
@@ -44,6 +55,22 @@ impl WorldCloner {
     }
 
     pub fn register_clone_type<T: WorldClonableTrait>(&mut self) {
+        self.register_clone_type_inner::<T>(None);
+    }
+
+    /// Like [WorldCloner::register_clone_type] but also lets [WorldDiff]s
+    /// detect when the component's value changes.
+    /// Otherwise only adding and removing the component is detected.
+    pub fn register_diffable_type<T: WorldClonableTrait + PartialEq>(&mut self) {
+        self.register_clone_type_inner::<T>(Some(|world_a, entity_a, world_b, entity_b| {
+            *world_a.get::<&T>(entity_a).unwrap() == *world_b.get::<&T>(entity_b).unwrap()
+        }));
+    }
+
+    fn register_clone_type_inner<T: WorldClonableTrait>(
+        &mut self,
+        equals: Option<fn(&hecs::World, Entity, &hecs::World, Entity) -> bool>,
+    ) {
         self.cloners.insert(
             std::any::TypeId::of::<T>(),
             RegisteredComponent {
@@ -57,16 +84,49 @@ impl WorldCloner {
                         let _ = writer.push(c.clone_with_context(entity_migrator));
                     }
                 },
+                snapshot_functions: SnapshotFunctions::new::<T>(equals),
             },
         );
     }
 
+    /// The name of a registered component type.
+    pub fn type_name(&self, type_id: TypeId) -> Option<&'static str> {
+        self.cloners
+            .get(&type_id)
+            .map(|c| c.snapshot_functions.type_name())
+    }
+
+    pub(crate) fn snapshot_functions(&self, type_id: TypeId) -> Option<&SnapshotFunctions> {
+        self.cloners.get(&type_id).map(|c| &c.snapshot_functions)
+    }
+
     pub fn clone_world(
         &mut self,
         source_world: &mut World,
         destination_world: &mut World,
     ) -> EntityMigrator {
-        let mut reserved_entities = destination_world.reserve_entities(source_world.len());
+        self.clone_world_inner(source_world, destination_world, false)
+    }
+
+    /// Clones `source_world` into an empty `destination_world`
+    /// keeping the same [Entity] handles.
+    pub(crate) fn clone_world_preserving_entities(
+        &mut self,
+        source_world: &mut World,
+        destination_world: &mut World,
+    ) {
+        assert!(destination_world.is_empty());
+        self.clone_world_inner(source_world, destination_world, true);
+    }
+
+    fn clone_world_inner(
+        &mut self,
+        source_world: &mut World,
+        destination_world: &mut World,
+        preserve_entities: bool,
+    ) -> EntityMigrator {
+        let mut reserved_entities =
+            destination_world.reserve_entities(if preserve_entities { 0 } else { source_world.len() });
 
         self.old_to_new_entities.clear();
         self.old_to_new_entities
@@ -75,16 +135,21 @@ impl WorldCloner {
         let mut old_to_new_temp = Vec::new();
 
         for entity in source_world.iter() {
-            let index = entity.entity().id() as usize;
+            let entity = entity.entity();
+            let index = entity.id() as usize;
             self.old_to_new_entities
-                .resize(index.max(self.old_to_new_entities.len()), None);
-            self.old_to_new_entities[index] = Some(reserved_entities.next().unwrap());
+                .resize((index + 1).max(self.old_to_new_entities.len()), None);
+            self.old_to_new_entities[index] = Some(if preserve_entities {
+                entity
+            } else {
+                reserved_entities.next().unwrap()
+            });
         }
 
         destination_world.flush();
 
         let entity_migrator = EntityMigrator {
-            old_to_new_entities: &self.old_to_new_entities,
+            old_to_new_entities: Some(&self.old_to_new_entities),
         };
 
         for archetype in source_world.archetypes() {
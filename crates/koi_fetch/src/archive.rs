@@ -0,0 +1,305 @@
+//! A simple archive format for shipping many asset files as one.
+//!
+//! Layout, with all integers little-endian:
+//! - The magic bytes `KOIPACK1` and a `u32` entry count.
+//! - For each entry: a `u32` path length, the UTF-8 path, then `u64` offset,
+//!   `u64` stored length, `u64` original length and a `u8` compression flag.
+//! - The concatenated file contents. Offsets are relative to the end of the index.
+//!
+//! Mount an [Archive] with [crate::mount] and [AssetSource::Archive](crate::AssetSource::Archive)
+//! so [crate::fetch_bytes] reads from it.
+
+use std::collections::HashMap;
+
+const MAGIC: &[u8; 8] = b"KOIPACK1";
+/// A path length, offset, stored length, original length, and compressed flag.
+const MIN_ENTRY_SIZE: usize = 4 + 8 * 3 + 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveError {
+    /// The archive itself couldn't be read.
+    Fetch,
+    /// The bytes don't start with the archive's magic bytes.
+    NotAnArchive,
+    /// The index or a file points past the end of the archive.
+    Truncated,
+    /// A file is compressed but compression support isn't enabled, or the data is corrupt.
+    Decompress,
+}
+
+impl std::fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fetch => write!(f, "could not read asset archive"),
+            Self::NotAnArchive => write!(f, "not a koi asset archive"),
+            Self::Truncated => write!(f, "asset archive is truncated"),
+            Self::Decompress => write!(f, "could not decompress file in asset archive"),
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {}
+
+struct ArchiveEntry {
+    offset: usize,
+    stored_length: usize,
+    original_length: usize,
+    compressed: bool,
+}
+
+/// A read-only set of files packed by an [ArchiveWriter].
+pub struct Archive {
+    bytes: Vec<u8>,
+    data_start: usize,
+    entries: HashMap<String, ArchiveEntry>,
+}
+
+impl Archive {
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, ArchiveError> {
+        let mut reader = ByteReader {
+            bytes: &bytes,
+            position: 0,
+        };
+        if reader.read(MAGIC.len())? != MAGIC {
+            return Err(ArchiveError::NotAnArchive);
+        }
+
+        let entry_count = reader.read_u32()?;
+        // The count comes from the file, so don't reserve more entries than could fit in it.
+        let remaining = bytes.len() - reader.position;
+        let mut entries =
+            HashMap::with_capacity((entry_count as usize).min(remaining / MIN_ENTRY_SIZE));
+        for _ in 0..entry_count {
+            let path_length = reader.read_u32()? as usize;
+            let path = std::str::from_utf8(reader.read(path_length)?)
+                .map_err(|_| ArchiveError::NotAnArchive)?
+                .to_string();
+            let entry = ArchiveEntry {
+                offset: reader.read_usize()?,
+                stored_length: reader.read_usize()?,
+                original_length: reader.read_usize()?,
+                compressed: reader.read(1)?[0] != 0,
+            };
+            entries.insert(path, entry);
+        }
+
+        let data_start = reader.position;
+        for entry in entries.values() {
+            // Checked so corrupt offsets can't overflow and pass the bounds check.
+            let end = data_start
+                .checked_add(entry.offset)
+                .and_then(|start| start.checked_add(entry.stored_length));
+            if end.is_none_or(|end| end > bytes.len()) {
+                return Err(ArchiveError::Truncated);
+            }
+        }
+
+        Ok(Self {
+            bytes,
+            data_start,
+            entries,
+        })
+    }
+
+    /// Reads an archive with a single request, which is much faster than many small
+    /// requests on web.
+    pub async fn fetch(path: &str) -> Result<Self, ArchiveError> {
+        let bytes = crate::fetch_bytes(path)
+            .await
+            .map_err(|_| ArchiveError::Fetch)?;
+        Self::from_bytes(bytes)
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(&crate::normalize_path(path))
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|p| p.as_str())
+    }
+
+    /// Returns the contents of a file, decompressing it if needed.
+    pub fn get(&self, path: &str) -> Option<Result<Vec<u8>, ArchiveError>> {
+        let entry = self.entries.get(&crate::normalize_path(path))?;
+        let start = self.data_start + entry.offset;
+        let stored = &self.bytes[start..start + entry.stored_length];
+        Some(if entry.compressed {
+            decompress(stored, entry.original_length)
+        } else {
+            Ok(stored.to_vec())
+        })
+    }
+}
+
+/// Packs files into an [Archive].
+#[derive(Default)]
+pub struct ArchiveWriter {
+    files: Vec<(String, Vec<u8>, usize, bool)>,
+    /// Compresses files if the `compression` feature is enabled.
+    pub compress: bool,
+}
+
+impl ArchiveWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_file(&mut self, path: &str, bytes: &[u8]) {
+        let path = crate::normalize_path(path);
+        let (stored, compressed) = if self.compress {
+            compress(bytes)
+        } else {
+            (bytes.to_vec(), false)
+        };
+        self.files.retain(|(p, ..)| *p != path);
+        self.files.push((path, stored, bytes.len(), compressed));
+    }
+
+    /// Adds every file in `directory` and its subdirectories,
+    /// with paths relative to `directory`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn add_directory(&mut self, directory: &std::path::Path) -> std::io::Result<()> {
+        fn add_recursive(
+            writer: &mut ArchiveWriter,
+            root: &std::path::Path,
+            directory: &std::path::Path,
+        ) -> std::io::Result<()> {
+            let mut entries = std::fs::read_dir(directory)?.collect::<Result<Vec<_>, _>>()?;
+            // Sort so the same directory always packs to the same bytes.
+            entries.sort_by_key(|e| e.path());
+            for entry in entries {
+                let path = entry.path();
+                if entry.file_type()?.is_dir() {
+                    add_recursive(writer, root, &path)?;
+                } else {
+                    let relative = path.strip_prefix(root).unwrap();
+                    writer.add_file(&relative.to_string_lossy(), &std::fs::read(&path)?);
+                }
+            }
+            Ok(())
+        }
+        add_recursive(self, directory, directory)
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        let mut index = Vec::new();
+        index.extend_from_slice(MAGIC);
+        index.extend_from_slice(&(self.files.len() as u32).to_le_bytes());
+
+        let mut data = Vec::new();
+        for (path, stored, original_length, compressed) in self.files {
+            index.extend_from_slice(&(path.len() as u32).to_le_bytes());
+            index.extend_from_slice(path.as_bytes());
+            index.extend_from_slice(&(data.len() as u64).to_le_bytes());
+            index.extend_from_slice(&(stored.len() as u64).to_le_bytes());
+            index.extend_from_slice(&(original_length as u64).to_le_bytes());
+            index.push(compressed as u8);
+            data.extend_from_slice(&stored);
+        }
+
+        index.extend_from_slice(&data);
+        index
+    }
+}
+
+/// Packs every file in `directory` into an archive.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn pack_directory(directory: &std::path::Path, compress: bool) -> std::io::Result<Vec<u8>> {
+    let mut writer = ArchiveWriter {
+        compress,
+        ..Default::default()
+    };
+    writer.add_directory(directory)?;
+    Ok(writer.finish())
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn read(&mut self, length: usize) -> Result<&'a [u8], ArchiveError> {
+        let end = self
+            .position
+            .checked_add(length)
+            .ok_or(ArchiveError::Truncated)?;
+        let bytes = self
+            .bytes
+            .get(self.position..end)
+            .ok_or(ArchiveError::Truncated)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, ArchiveError> {
+        Ok(u32::from_le_bytes(self.read(4)?.try_into().unwrap()))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, ArchiveError> {
+        Ok(u64::from_le_bytes(self.read(8)?.try_into().unwrap()))
+    }
+
+    /// Reads a `u64` that must fit in memory, such as an offset or a length.
+    fn read_usize(&mut self) -> Result<usize, ArchiveError> {
+        usize::try_from(self.read_u64()?).map_err(|_| ArchiveError::Truncated)
+    }
+}
+
+/// Returns the stored bytes and whether they're compressed.
+/// Files that don't get smaller are stored uncompressed.
+#[cfg(feature = "compression")]
+fn compress(bytes: &[u8]) -> (Vec<u8>, bool) {
+    let compressed = miniz_oxide::deflate::compress_to_vec(bytes, 6);
+    if compressed.len() < bytes.len() {
+        (compressed, true)
+    } else {
+        (bytes.to_vec(), false)
+    }
+}
+
+#[cfg(not(feature = "compression"))]
+fn compress(bytes: &[u8]) -> (Vec<u8>, bool) {
+    (bytes.to_vec(), false)
+}
+
+#[cfg(feature = "compression")]
+fn decompress(bytes: &[u8], original_length: usize) -> Result<Vec<u8>, ArchiveError> {
+    miniz_oxide::inflate::decompress_to_vec_with_limit(bytes, original_length)
+        .map_err(|_| ArchiveError::Decompress)
+}
+
+#[cfg(not(feature = "compression"))]
+fn decompress(_bytes: &[u8], _original_length: usize) -> Result<Vec<u8>, ArchiveError> {
+    Err(ArchiveError::Decompress)
+}
+
+#[test]
+fn archive_round_trip_test() {
+    let mut writer = ArchiveWriter::new();
+    writer.add_file("./textures/a.png", &[1, 2, 3]);
+    writer.add_file("b.txt", b"hello");
+    let archive = Archive::from_bytes(writer.finish()).unwrap();
+
+    assert_eq!(archive.get("textures/a.png"), Some(Ok(vec![1, 2, 3])));
+    assert_eq!(archive.get("b.txt"), Some(Ok(b"hello".to_vec())));
+    assert_eq!(archive.get("c.txt"), None);
+    assert_eq!(
+        Archive::from_bytes(b"KOIPACK0".to_vec()).err(),
+        Some(ArchiveError::NotAnArchive)
+    );
+
+    // An offset near `usize::MAX` must not wrap around the bounds check.
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend(1u32.to_le_bytes());
+    bytes.extend(1u32.to_le_bytes());
+    bytes.push(b'a');
+    bytes.extend(u64::MAX.to_le_bytes());
+    bytes.extend(2u64.to_le_bytes());
+    bytes.extend(2u64.to_le_bytes());
+    bytes.push(0);
+    assert_eq!(
+        Archive::from_bytes(bytes).err(),
+        Some(ArchiveError::Truncated)
+    );
+}
@@ -0,0 +1,32 @@
+//! Files compiled into the binary and read with paths like "embedded://ui/font.png".
+
+pub const EMBEDDED_SCHEME: &str = "embedded";
+
+/// Makes `bytes` readable at "embedded://{path}".
+///
+/// The file is added to an [AssetSource::Embedded](crate::AssetSource::Embedded)
+/// mounted at "embedded://". Usually called through [embed_asset!](crate::embed_asset).
+pub fn register_embedded(path: &str, bytes: &'static [u8]) {
+    crate::mounts::mount_embedded_file(path, bytes);
+}
+
+/// Embeds a file with `include_bytes!` and registers it so it can be read at
+/// "embedded://{path}" on every platform.
+///
+/// The file is located relative to the current source file, like `include_bytes!`.
+///
+/// ```ignore
+/// // Readable as "embedded://ui/font.png".
+/// koi_fetch::embed_asset!("ui/font.png", "../assets/ui/font.png");
+/// // Readable as "embedded://../assets/ship.bin".
+/// koi_fetch::embed_asset!("../assets/ship.bin");
+/// ```
+#[macro_export]
+macro_rules! embed_asset {
+    ($file:literal) => {
+        $crate::register_embedded($file, include_bytes!($file))
+    };
+    ($path:expr, $file:literal) => {
+        $crate::register_embedded($path, include_bytes!($file))
+    };
+}
@@ -0,0 +1,49 @@
+/// Why [crate::fetch_bytes] couldn't read a path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FetchError {
+    NotFound,
+    PermissionDenied,
+    /// A request failed or the server responded with an error status.
+    Network(String),
+    /// The file is larger than [crate::FetchOptions::max_bytes].
+    TooLarge { max_bytes: usize },
+    Other(String),
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "file not found"),
+            Self::PermissionDenied => write!(f, "permission denied"),
+            Self::Network(reason) => write!(f, "network error: {reason}"),
+            Self::TooLarge { max_bytes } => write!(f, "file is larger than {max_bytes} bytes"),
+            Self::Other(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+impl From<std::io::Error> for FetchError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => Self::NotFound,
+            std::io::ErrorKind::PermissionDenied => Self::PermissionDenied,
+            // Errors from reading are wrapped so they survive `std::io::Read`.
+            _ => match error.get_ref().and_then(|e| e.downcast_ref::<FetchError>()) {
+                Some(fetch_error) => fetch_error.clone(),
+                None => Self::Other(error.to_string()),
+            },
+        }
+    }
+}
+
+impl From<FetchError> for std::io::Error {
+    fn from(error: FetchError) -> Self {
+        match error {
+            FetchError::NotFound => std::io::ErrorKind::NotFound.into(),
+            FetchError::PermissionDenied => std::io::ErrorKind::PermissionDenied.into(),
+            error => std::io::Error::other(error),
+        }
+    }
+}
@@ -1,27 +1,265 @@
+mod archive;
+pub use archive::*;
+
+mod embedded;
+pub use embedded::*;
+
+mod fetch_error;
+pub use fetch_error::*;
+
+mod mounts;
+pub use mounts::*;
+
+mod path;
+pub use path::*;
+
+use mounts::{read_mounted, resolve, Resolved};
+use std::io::Read;
+use std::sync::Arc;
+
+/// Reported while reading a file with [FetchOptions::on_progress].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FetchProgress {
+    pub bytes_read: usize,
+    /// `None` if the size isn't known ahead of time.
+    pub total_bytes: Option<usize>,
+}
+
+#[derive(Clone)]
+pub struct FetchOptions {
+    /// Reads fail with [FetchError::TooLarge] past this many bytes.
+    /// Defaults to 100 MB.
+    pub max_bytes: usize,
+    /// Called as data is read. Useful for showing progress of large downloads.
+    pub on_progress: Option<Arc<dyn Fn(FetchProgress) + Send + Sync>>,
+}
+
+impl Default for FetchOptions {
+    fn default() -> Self {
+        Self {
+            max_bytes: 100_000_000,
+            on_progress: None,
+        }
+    }
+}
+
+/// Reads the bytes of a path, resolving it against mounted [AssetSource]s and the asset root.
+pub async fn fetch_bytes(path: &str) -> Result<Vec<u8>, FetchError> {
+    fetch_bytes_with_options(path, &FetchOptions::default()).await
+}
+
+pub async fn fetch_bytes_with_options(
+    path: &str,
+    options: &FetchOptions,
+) -> Result<Vec<u8>, FetchError> {
+    let mut reader = fetch_reader(path, options).await?;
+    let mut bytes = Vec::with_capacity(reader.total_bytes.unwrap_or(0));
+    reader.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Opens a path for incremental reading, so large files needn't be held in memory at once.
+///
+/// On web the entire file is downloaded and buffered before this returns, because the
+/// browser fetch API used here only provides the finished response body.
+/// [FetchOptions::max_bytes] and [FetchOptions::on_progress] still apply to reading it.
+pub async fn fetch_reader(path: &str, options: &FetchOptions) -> Result<FetchReader, FetchError> {
+    let mut error = FetchError::NotFound;
+    for resolved in resolve(path) {
+        let (reader, total_bytes): (Box<dyn Read + Send>, _) = match resolved {
+            Resolved::Static(bytes) => (Box::new(bytes), Some(bytes.len())),
+            Resolved::Mounted { prefix, relative } => {
+                let bytes = read_mounted(&prefix, &relative)?;
+                let length = bytes.len();
+                (Box::new(std::io::Cursor::new(bytes)), Some(length))
+            }
+            // Any error falls through to later mounts and the asset root.
+            // If every candidate fails the last error other than NotFound is returned.
+            Resolved::File(file) => match open_file(&file).await {
+                Ok(result) => result,
+                Err(FetchError::NotFound) => continue,
+                Err(e) => {
+                    error = e;
+                    continue;
+                }
+            },
+        };
+
+        if let Some(total_bytes) = total_bytes {
+            if total_bytes > options.max_bytes {
+                return Err(FetchError::TooLarge {
+                    max_bytes: options.max_bytes,
+                });
+            }
+        }
+        return Ok(FetchReader {
+            reader,
+            bytes_read: 0,
+            total_bytes,
+            max_bytes: options.max_bytes,
+            on_progress: options.on_progress.clone(),
+        });
+    }
+    Err(error)
+}
+
+/// Reads a fetched file, enforcing [FetchOptions::max_bytes] and reporting progress.
+pub struct FetchReader {
+    reader: Box<dyn Read + Send>,
+    bytes_read: usize,
+    total_bytes: Option<usize>,
+    max_bytes: usize,
+    on_progress: Option<Arc<dyn Fn(FetchProgress) + Send + Sync>>,
+}
+
+impl FetchReader {
+    /// The size of the file, if known ahead of time.
+    pub fn total_bytes(&self) -> Option<usize> {
+        self.total_bytes
+    }
+}
+
+impl Read for FetchReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.bytes_read += read;
+        if self.bytes_read > self.max_bytes {
+            return Err(FetchError::TooLarge {
+                max_bytes: self.max_bytes,
+            }
+            .into());
+        }
+        if let Some(on_progress) = &self.on_progress {
+            on_progress(FetchProgress {
+                bytes_read: self.bytes_read,
+                total_bytes: self.total_bytes,
+            });
+        }
+        Ok(read)
+    }
+}
+
+type OpenedFile = (Box<dyn Read + Send>, Option<usize>);
+
 #[cfg(not(target_arch = "wasm32"))]
-pub async fn fetch_bytes(path: &str) -> Result<Vec<u8>, ()> {
+async fn open_file(path: &str) -> Result<OpenedFile, FetchError> {
     #[cfg(feature = "network_requests")]
-    let contents = if path.starts_with("http") {
-        use std::io::Read;
-
-        let response = ureq::get(path).call().map_err(|_| ())?;
-
-        let mut bytes: Vec<u8> = Vec::new();
-        response
-            .into_reader()
-            .take(10_000_000)
-            .read_to_end(&mut bytes)
-            .map_err(|_| ())?;
-        bytes
-    } else {
-        std::fs::read(path).map_err(|_| ())?
-    };
-    #[cfg(not(feature = "network_requests"))]
-    let contents = std::fs::read(path).map_err(|_| ())?;
-    Ok(contents)
+    if is_url(path) {
+        let response = ureq::get(path).call().map_err(|error| match error {
+            ureq::Error::Status(404 | 410, _) => FetchError::NotFound,
+            ureq::Error::Status(401 | 403, _) => FetchError::PermissionDenied,
+            ureq::Error::Status(status, _) => FetchError::Network(format!("HTTP status {status}")),
+            ureq::Error::Transport(transport) => FetchError::Network(transport.to_string()),
+        })?;
+        let total_bytes = response
+            .header("Content-Length")
+            .and_then(|length| length.parse().ok());
+        return Ok((response.into_reader(), total_bytes));
+    }
+
+    if is_url(path) {
+        return Err(FetchError::Network(
+            "the `network_requests` feature is disabled".into(),
+        ));
+    }
+
+    let file = std::fs::File::open(path)?;
+    let total_bytes = file.metadata().ok().map(|m| m.len() as usize);
+    Ok((Box::new(file), total_bytes))
 }
 
 #[cfg(target_arch = "wasm32")]
-pub async fn fetch_bytes(path: &str) -> Result<Vec<u8>, ()> {
-    kwasm::libraries::fetch(path).await
+async fn open_file(path: &str) -> Result<OpenedFile, FetchError> {
+    // The browser doesn't report why a request failed. Missing files are by far the most
+    // common cause, and reporting them as NotFound keeps the same behavior as native.
+    let bytes = kwasm::libraries::fetch(path)
+        .await
+        .map_err(|_| FetchError::NotFound)?;
+    let length = bytes.len();
+    Ok((Box::new(std::io::Cursor::new(bytes)), Some(length)))
+}
+
+#[cfg(all(test, feature = "network_requests"))]
+fn serve_once(response: &'static [u8]) -> String {
+    use std::io::Write;
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0; 1024];
+        let _ = stream.read(&mut request);
+        stream.write_all(response).unwrap();
+    });
+    format!("http://{address}/file.bin")
+}
+
+/// Native fetches never wait, so polling once is enough.
+#[cfg(test)]
+fn poll_once<F: std::future::Future>(future: F) -> F::Output {
+    struct NoopWaker;
+    impl std::task::Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+    let waker = std::task::Waker::from(Arc::new(NoopWaker));
+    let mut future = Box::pin(future);
+    match future
+        .as_mut()
+        .poll(&mut std::task::Context::from_waker(&waker))
+    {
+        std::task::Poll::Ready(output) => output,
+        std::task::Poll::Pending => panic!("fetch unexpectedly pending"),
+    }
+}
+
+#[cfg(all(test, feature = "network_requests"))]
+#[test]
+fn fetch_http_test() {
+    let url = serve_once(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello");
+    let progress = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let progress_clone = progress.clone();
+    let options = FetchOptions {
+        on_progress: Some(Arc::new(move |p| progress_clone.lock().unwrap().push(p))),
+        ..Default::default()
+    };
+    assert_eq!(
+        poll_once(fetch_bytes_with_options(&url, &options)),
+        Ok(b"hello".to_vec())
+    );
+    assert_eq!(
+        progress.lock().unwrap().last(),
+        Some(&FetchProgress {
+            bytes_read: 5,
+            total_bytes: Some(5)
+        })
+    );
+
+    let url = serve_once(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n");
+    assert_eq!(poll_once(fetch_bytes(&url)), Err(FetchError::NotFound));
+
+    let url = serve_once(b"HTTP/1.1 200 OK\r\nContent-Length: 5\r\n\r\nhello");
+    let options = FetchOptions {
+        max_bytes: 4,
+        ..Default::default()
+    };
+    assert_eq!(
+        poll_once(fetch_bytes_with_options(&url, &options)),
+        Err(FetchError::TooLarge { max_bytes: 4 })
+    );
+}
+
+#[test]
+fn fetch_missing_file_test() {
+    assert_eq!(
+        poll_once(fetch_bytes("this/file/does/not/exist.txt")),
+        Err(FetchError::NotFound)
+    );
+}
+
+#[test]
+fn fetch_embedded_test() {
+    register_embedded("models/ship.bin", &[1, 2, 3]);
+    let path = join_relative("embedded://models/ship.gltf", "./ship.bin");
+    assert_eq!(poll_once(fetch_bytes(&path)), Ok(vec![1, 2, 3]));
+    assert!(is_in_memory(&path));
 }
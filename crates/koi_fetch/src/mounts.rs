@@ -0,0 +1,237 @@
+//! Mountable sources that [crate::fetch_bytes] resolves paths against.
+//!
+//! The same game code can load from loose files in development
+//! and from a packaged source in release by changing what's mounted.
+
+use crate::path::{is_url, normalize_path, split_scheme};
+use crate::FetchError;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// Somewhere files can be read from.
+pub enum AssetSource {
+    /// Files in a directory on disk, or relative to the page's URL on web.
+    Directory(String),
+    /// Files held in memory. Mount again at the same prefix to replace them.
+    Memory(HashMap<String, Vec<u8>>),
+    /// Files compiled into the binary, usually with `include_bytes!`.
+    ///
+    /// Files registered with [crate::register_embedded] are in one of these mounted at "embedded://".
+    Embedded(HashMap<String, &'static [u8]>),
+    /// Files packed into a [crate::Archive].
+    Archive(crate::Archive),
+}
+
+struct Mounts {
+    asset_root: String,
+    /// Sorted so that longer prefixes are checked first.
+    mounts: Vec<(String, AssetSource)>,
+}
+
+static MOUNTS: RwLock<Mounts> = RwLock::new(Mounts {
+    asset_root: String::new(),
+    mounts: Vec::new(),
+});
+
+/// Sets the directory that paths not found in any mount are read relative to.
+///
+/// Defaults to "", which is the working directory on native and the page's URL on web.
+pub fn set_asset_root(asset_root: &str) {
+    MOUNTS.write().unwrap().asset_root = normalize_path(asset_root);
+}
+
+pub fn asset_root() -> String {
+    MOUNTS.read().unwrap().asset_root.clone()
+}
+
+/// Makes paths starting with `prefix` resolve against `source`.
+///
+/// If a file isn't found in a mount, shorter prefixes and then the asset root are tried.
+/// Mount at "" to overlay the entire asset root.
+pub fn mount(prefix: &str, source: AssetSource) {
+    let prefix = normalize_path(prefix);
+    let mut mounts = MOUNTS.write().unwrap();
+    mounts.mounts.retain(|(p, _)| *p != prefix);
+    mounts.mounts.push((prefix, source));
+    mounts
+        .mounts
+        .sort_by_key(|(p, _)| std::cmp::Reverse(p.len()));
+}
+
+/// Adds a file to the [AssetSource::Embedded] mounted at "embedded://", mounting it if needed.
+pub(crate) fn mount_embedded_file(path: &str, bytes: &'static [u8]) {
+    let prefix = format!("{}://", crate::EMBEDDED_SCHEME);
+    let path = normalize_path(path);
+    {
+        let mut mounts = MOUNTS.write().unwrap();
+        if let Some((_, AssetSource::Embedded(files))) =
+            mounts.mounts.iter_mut().find(|(p, _)| *p == prefix)
+        {
+            files.insert(path, bytes);
+            return;
+        }
+    }
+    mount(
+        &prefix,
+        AssetSource::Embedded(HashMap::from([(path, bytes)])),
+    );
+}
+
+/// Returns `false` if nothing was mounted at `prefix`.
+pub fn unmount(prefix: &str) -> bool {
+    let prefix = normalize_path(prefix);
+    let mut mounts = MOUNTS.write().unwrap();
+    let len = mounts.mounts.len();
+    mounts.mounts.retain(|(p, _)| *p != prefix);
+    mounts.mounts.len() != len
+}
+
+/// Somewhere the bytes of a path may be found. Found without reading any file contents.
+pub(crate) enum Resolved {
+    /// Compiled into the binary.
+    Static(&'static [u8]),
+    /// A file known to be in the [AssetSource::Memory] or [AssetSource::Archive] mounted at `prefix`.
+    Mounted { prefix: String, relative: String },
+    /// A file on disk or a URL, which may not exist.
+    File(String),
+}
+
+impl Resolved {
+    fn is_in_memory(&self) -> bool {
+        !matches!(self, Self::File(_))
+    }
+}
+
+fn strip_mount_prefix<'a>(path: &'a str, prefix: &str) -> Option<&'a str> {
+    if prefix.is_empty() {
+        return Some(path);
+    }
+    let rest = path.strip_prefix(prefix)?;
+    // Prefixes like "embedded://" already end with a separator.
+    if rest.is_empty() || prefix.ends_with('/') {
+        Some(rest)
+    } else {
+        rest.strip_prefix('/')
+    }
+}
+
+fn join(directory: &str, path: &str) -> String {
+    if directory.is_empty() {
+        path.into()
+    } else {
+        format!("{}/{}", directory, path)
+    }
+}
+
+/// The places to try reading a path from, in order.
+///
+/// Stops at the first file found in memory, since nothing after it would be read.
+pub(crate) fn resolve(path: &str) -> Vec<Resolved> {
+    if is_url(path) {
+        return vec![Resolved::File(path.into())];
+    }
+
+    let path = normalize_path(path);
+    // Paths like "embedded://ui/font.png" are only found in mounts with the same scheme.
+    let has_scheme = split_scheme(&path).is_some();
+
+    let mounts = MOUNTS.read().unwrap();
+    let mut resolved = Vec::new();
+    for (prefix, source) in &mounts.mounts {
+        if has_scheme && split_scheme(prefix).is_none() {
+            continue;
+        }
+        let Some(relative) = strip_mount_prefix(&path, prefix) else {
+            continue;
+        };
+        let found = match source {
+            AssetSource::Directory(directory) => {
+                resolved.push(Resolved::File(join(directory, relative)));
+                continue;
+            }
+            AssetSource::Memory(files) => files.contains_key(relative),
+            AssetSource::Embedded(files) => {
+                if let Some(bytes) = files.get(relative) {
+                    resolved.push(Resolved::Static(bytes));
+                    return resolved;
+                }
+                false
+            }
+            AssetSource::Archive(archive) => archive.contains(relative),
+        };
+        if found {
+            resolved.push(Resolved::Mounted {
+                prefix: prefix.clone(),
+                relative: relative.into(),
+            });
+            return resolved;
+        }
+    }
+
+    if has_scheme {
+        return resolved;
+    }
+    if path.starts_with('/') || std::path::Path::new(&path).is_absolute() {
+        resolved.push(Resolved::File(path));
+    } else {
+        resolved.push(Resolved::File(join(&mounts.asset_root, &path)));
+    }
+    resolved
+}
+
+/// Reads a file found by [resolve] in a mounted [AssetSource::Memory] or [AssetSource::Archive].
+pub(crate) fn read_mounted(prefix: &str, relative: &str) -> Result<Vec<u8>, FetchError> {
+    let mounts = MOUNTS.read().unwrap();
+    // The source may have been unmounted since the path was resolved.
+    let source = mounts
+        .mounts
+        .iter()
+        .find(|(p, _)| p == prefix)
+        .map(|(_, source)| source)
+        .ok_or(FetchError::NotFound)?;
+    match source {
+        AssetSource::Memory(files) => files.get(relative).cloned().ok_or(FetchError::NotFound),
+        AssetSource::Archive(archive) => archive
+            .get(relative)
+            .ok_or(FetchError::NotFound)?
+            .map_err(|error| FetchError::Other(format!("{relative}: {error}"))),
+        AssetSource::Directory(_) | AssetSource::Embedded(_) => Err(FetchError::NotFound),
+    }
+}
+
+/// Returns `true` if a path is read from memory rather than from disk or the network.
+pub fn is_in_memory(path: &str) -> bool {
+    resolve(path)
+        .first()
+        .is_some_and(|resolved| resolved.is_in_memory())
+}
+
+/// The path or URL of the first file a path may be read from.
+///
+/// Used on web where some files are loaded by the browser directly.
+pub fn resolve_file_path_unchecked(path: &str) -> Option<String> {
+    resolve(path)
+        .into_iter()
+        .find_map(|resolved| match resolved {
+            Resolved::File(file) => Some(file),
+            _ => None,
+        })
+}
+
+/// The file on disk that a path currently resolves to, if any.
+///
+/// Used to watch loaded files for changes.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn resolve_file_path(path: &str) -> Option<String> {
+    for resolved in resolve(path) {
+        match resolved {
+            Resolved::File(file) => {
+                if std::path::Path::new(&file).exists() {
+                    return Some(file);
+                }
+            }
+            _ => return None,
+        }
+    }
+    None
+}
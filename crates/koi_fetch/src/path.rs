@@ -0,0 +1,86 @@
+/// Returns `true` for paths like "https://example.com/a.png" that aren't resolved against mounts.
+pub fn is_url(path: &str) -> bool {
+    path.starts_with("http://") || path.starts_with("https://")
+}
+
+/// Normalizes an asset path so that equivalent paths compare equal.
+///
+/// Backslashes become forward slashes, empty and "." segments are removed
+/// and ".." segments are resolved where possible.
+/// "./textures/../a.png" and "a.png" both normalize to "a.png".
+pub fn normalize_path(path: &str) -> String {
+    if is_url(path) {
+        return path.into();
+    }
+    if let Some((scheme, rest)) = split_scheme(path) {
+        return format!("{}://{}", scheme, normalize_path(rest.trim_start_matches('/')));
+    }
+
+    let path = path.replace('\\', "/");
+    let is_absolute = path.starts_with('/');
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in path.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                if matches!(segments.last(), Some(s) if *s != "..") {
+                    segments.pop();
+                } else if !is_absolute {
+                    segments.push("..");
+                }
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    let joined = segments.join("/");
+    if is_absolute {
+        format!("/{}", joined)
+    } else {
+        joined
+    }
+}
+
+/// Splits paths like "embedded://ui/font.png" into "embedded" and "ui/font.png".
+pub fn split_scheme(path: &str) -> Option<(&str, &str)> {
+    let (scheme, rest) = path.split_once("://")?;
+    if !scheme.is_empty() && scheme.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Some((scheme, rest))
+    } else {
+        None
+    }
+}
+
+/// Resolves `relative` against the directory containing `base_file`.
+///
+/// Used for files that reference other files, like a glTF's buffers and textures.
+pub fn join_relative(base_file: &str, relative: &str) -> String {
+    if split_scheme(relative).is_some() || relative.starts_with('/') {
+        return normalize_path(relative);
+    }
+    if let Some((scheme, base_file)) = split_scheme(base_file) {
+        if !is_url(&format!("{}://", scheme)) {
+            return format!("{}://{}", scheme, join_relative(base_file, relative));
+        }
+    }
+    match base_file.rfind(['/', '\\']) {
+        Some(index) => normalize_path(&format!("{}/{}", &base_file[..index], relative)),
+        None => normalize_path(relative),
+    }
+}
+
+#[test]
+fn normalize_path_test() {
+    assert_eq!(normalize_path("./a.png"), "a.png");
+    assert_eq!(normalize_path("textures\\..\\a.png"), "a.png");
+    assert_eq!(normalize_path("../a//b/./c.png"), "../a/b/c.png");
+    assert_eq!(normalize_path("/assets/../a.png"), "/a.png");
+    assert_eq!(join_relative("models/ship.gltf", "ship.bin"), "models/ship.bin");
+    assert_eq!(join_relative("ship.gltf", "./textures/a.png"), "textures/a.png");
+    assert_eq!(normalize_path("embedded://./ui//font.png"), "embedded://ui/font.png");
+    assert_eq!(
+        join_relative("embedded://ship.gltf", "ship.bin"),
+        "embedded://ship.bin"
+    );
+}
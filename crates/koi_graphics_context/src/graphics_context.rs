@@ -146,7 +146,7 @@ impl GraphicsContext {
             (0, 0, 0),
         );
         self.texture_size_pixels[handle.inner().index as usize] = (width, height, depth);
-        Texture(handle)
+        Texture(handle, (width, height, depth))
     }
 
     pub unsafe fn new_texture_with_bytes(
@@ -351,6 +351,6 @@ impl GraphicsContext {
         );
         self.texture_size_pixels[handle.inner().index as usize] = (width, height, 1);
 
-        Texture(handle)
+        Texture(handle, (width, height, 1))
     }
 }
@@ -46,8 +46,26 @@ impl Default for PipelineSettings {
         }
     }
 }
+/// The second field is the width, height, and depth in pixels.
 #[derive(Clone)]
-pub struct Texture(Handle<TextureInner>);
+pub struct Texture(Handle<TextureInner>, (u32, u32, u32));
+
+impl Texture {
+    /// The width, height, and depth in pixels.
+    pub fn size(&self) -> (u32, u32, u32) {
+        self.1
+    }
+
+    pub fn pixel_format(&self) -> PixelFormat {
+        self.0.inner().pixel_format
+    }
+
+    /// Roughly how much memory the texture uses, not including mipmaps.
+    pub fn byte_size(&self) -> usize {
+        let (width, height, depth) = self.1;
+        width as usize * height as usize * depth as usize * self.pixel_format().bytes_per_pixel()
+    }
+}
 
 #[derive(Clone)]
 pub struct TextureInner {
@@ -62,6 +80,12 @@ pub struct TextureInner {
 
 pub struct CubeMap(Handle<CubeMapInner>);
 
+impl CubeMap {
+    pub fn pixel_format(&self) -> PixelFormat {
+        self.0.inner().pixel_format
+    }
+}
+
 #[derive(Clone)]
 pub struct CubeMapInner {
     index: u32,
@@ -102,13 +126,13 @@ impl TextureDataTrait for kmath::Vec4 {
     const PIXEL_FORMAT: PixelFormat = PixelFormat::RGBA32F;
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FilterMode {
     Nearest,
     Linear,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum WrappingMode {
     ClampToEdge,
     Repeat,
@@ -130,7 +154,21 @@ pub enum PixelFormat {
     // RGB32F,
 }
 
-#[derive(Clone, Copy, Debug)]
+impl PixelFormat {
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            Self::R8Unorm => 1,
+            Self::RG8Unorm | Self::Depth16 => 2,
+            Self::RGB8Unorm => 3,
+            // 24 bit depth is usually padded to 32 bits.
+            Self::RGBA8Unorm | Self::Depth24 | Self::Depth32F => 4,
+            Self::RGBA16F => 8,
+            Self::RGBA32F => 16,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FacesToRender {
     Front,
     Back,
@@ -138,7 +176,7 @@ pub enum FacesToRender {
     None,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// Specifies if a pixel will be rendered based on the z-buffer value.
 pub enum DepthTest {
     /// Effectively disables depth testing.
@@ -154,7 +192,7 @@ pub enum DepthTest {
 }
 
 /// This should be expanded
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BlendFactor {
     /// source_pixel
     One,
@@ -164,7 +202,7 @@ pub enum BlendFactor {
     OneMinusSourceAlpha,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct TextureSettings {
     pub srgb: bool,
     pub minification_filter: FilterMode,
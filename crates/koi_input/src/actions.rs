@@ -0,0 +1,625 @@
+use kapp::{Key, PointerButton};
+use std::collections::{HashMap, HashSet};
+
+/// A single key or pointer button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputSource {
+    Key(Key),
+    PointerButton(PointerButton),
+}
+
+/// What triggers an action.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Binding {
+    Button(InputSource),
+    /// An axis from -1.0 to 1.0, like A and D for moving left and right.
+    Axis {
+        negative: InputSource,
+        positive: InputSource,
+    },
+    /// A 2D axis, like WASD for moving.
+    Axis2D {
+        up: InputSource,
+        down: InputSource,
+        left: InputSource,
+        right: InputSource,
+    },
+}
+
+impl Binding {
+    pub fn key(key: Key) -> Self {
+        Self::Button(InputSource::Key(key))
+    }
+
+    pub fn pointer_button(button: PointerButton) -> Self {
+        Self::Button(InputSource::PointerButton(button))
+    }
+
+    pub fn wasd() -> Self {
+        Self::Axis2D {
+            up: InputSource::Key(Key::W),
+            down: InputSource::Key(Key::S),
+            left: InputSource::Key(Key::A),
+            right: InputSource::Key(Key::D),
+        }
+    }
+
+    pub fn arrow_keys() -> Self {
+        Self::Axis2D {
+            up: InputSource::Key(Key::Up),
+            down: InputSource::Key(Key::Down),
+            left: InputSource::Key(Key::Left),
+            right: InputSource::Key(Key::Right),
+        }
+    }
+
+    fn sources(&self) -> impl Iterator<Item = InputSource> {
+        let sources: Vec<InputSource> = match *self {
+            Self::Button(source) => vec![source],
+            Self::Axis { negative, positive } => vec![negative, positive],
+            Self::Axis2D {
+                up,
+                down,
+                left,
+                right,
+            } => vec![up, down, left, right],
+        };
+        sources.into_iter()
+    }
+}
+
+/// Named actions bound to keys and pointer buttons.
+///
+/// Gameplay code queries actions instead of specific keys so that bindings can be changed
+/// by the player:
+///
+/// ```ignore
+/// actions.bind("jump", Binding::key(Key::Space));
+/// actions.bind("move", Binding::wasd());
+/// // In FixedUpdate:
+/// if actions.pressed("jump") { /* ... */ }
+/// let (x, y) = actions.axis_2d("move");
+/// ```
+#[derive(Default)]
+pub struct Actions {
+    bindings: HashMap<String, Vec<Binding>>,
+    down: HashSet<InputSource>,
+    pressed: HashSet<InputSource>,
+    released: HashSet<InputSource>,
+    draw_pressed: HashSet<InputSource>,
+    draw_released: HashSet<InputSource>,
+    rebinding: Option<String>,
+    /// The press that completed a rebind, ignored until it's released.
+    rebind_press: Option<InputSource>,
+}
+
+impl Actions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a binding to an action. Actions can have multiple bindings.
+    pub fn bind(&mut self, action: &str, binding: Binding) {
+        let bindings = self.bindings.entry(action.into()).or_default();
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Replaces all of an action's bindings.
+    pub fn set_bindings(&mut self, action: &str, bindings: Vec<Binding>) {
+        self.bindings.insert(action.into(), bindings);
+    }
+
+    /// Replaces one of an action's bindings.
+    /// Returns `false` if the action didn't have the `old` binding.
+    pub fn rebind(&mut self, action: &str, old: &Binding, new: Binding) -> bool {
+        if let Some(binding) = self
+            .bindings
+            .get_mut(action)
+            .and_then(|bindings| bindings.iter_mut().find(|b| *b == old))
+        {
+            *binding = new;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn unbind_all(&mut self, action: &str) {
+        self.bindings.remove(action);
+    }
+
+    pub fn bindings(&self, action: &str) -> &[Binding] {
+        self.bindings.get(action).map_or(&[], |b| b.as_slice())
+    }
+
+    /// The next key or pointer button pressed replaces the action's bindings.
+    /// Useful for a "press a key" prompt in a settings menu.
+    ///
+    /// The captured press doesn't trigger any actions.
+    pub fn rebind_next_input(&mut self, action: &str) {
+        self.rebinding = Some(action.into());
+    }
+
+    /// Returns `true` while waiting for input after [Actions::rebind_next_input].
+    pub fn is_rebinding(&self) -> bool {
+        self.rebinding.is_some()
+    }
+
+    fn sources<'a>(&'a self, action: &str) -> impl Iterator<Item = InputSource> + 'a {
+        self.bindings(action).iter().flat_map(|b| b.sources())
+    }
+
+    /// Returns `true` if any of the action's bindings are held.
+    pub fn held(&self, action: &str) -> bool {
+        self.sources(action).any(|s| self.down.contains(&s))
+    }
+
+    /// Returns `true` if any of the action's bindings were pressed since the last fixed update.
+    pub fn pressed(&self, action: &str) -> bool {
+        self.sources(action).any(|s| self.pressed.contains(&s))
+    }
+
+    /// Returns `true` if any of the action's bindings were released since the last fixed update.
+    pub fn released(&self, action: &str) -> bool {
+        self.sources(action).any(|s| self.released.contains(&s))
+    }
+
+    /// Like [Actions::pressed] but for `Draw` handlers:
+    /// returns `true` if any of the action's bindings were pressed since the last draw.
+    pub fn pressed_this_draw(&self, action: &str) -> bool {
+        self.sources(action).any(|s| self.draw_pressed.contains(&s))
+    }
+
+    /// Like [Actions::released] but for `Draw` handlers.
+    pub fn released_this_draw(&self, action: &str) -> bool {
+        self.sources(action)
+            .any(|s| self.draw_released.contains(&s))
+    }
+
+    /// A value from -1.0 to 1.0.
+    /// [Binding::Button]s count as 1.0 when held and [Binding::Axis2D]s use their horizontal axis.
+    pub fn axis(&self, action: &str) -> f32 {
+        let value: f32 = self
+            .bindings(action)
+            .iter()
+            .map(|binding| match *binding {
+                Binding::Button(source) => self.value(source),
+                Binding::Axis { negative, positive } => self.value(positive) - self.value(negative),
+                Binding::Axis2D { left, right, .. } => self.value(right) - self.value(left),
+            })
+            .sum();
+        value.clamp(-1.0, 1.0)
+    }
+
+    /// An (x, y) value with a length of at most 1.0. Positive y is up.
+    pub fn axis_2d(&self, action: &str) -> (f32, f32) {
+        let (mut x, mut y) = (0.0, 0.0);
+        for binding in self.bindings(action) {
+            match *binding {
+                Binding::Button(_) => {}
+                Binding::Axis { negative, positive } => {
+                    x += self.value(positive) - self.value(negative)
+                }
+                Binding::Axis2D {
+                    up,
+                    down,
+                    left,
+                    right,
+                } => {
+                    x += self.value(right) - self.value(left);
+                    y += self.value(up) - self.value(down);
+                }
+            }
+        }
+        let length: f32 = (x * x + y * y).sqrt();
+        if length > 1.0 {
+            (x / length, y / length)
+        } else {
+            (x, y)
+        }
+    }
+
+    fn value(&self, source: InputSource) -> f32 {
+        if self.down.contains(&source) {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &kapp::Event) {
+        let (source, is_down) = match *event {
+            kapp::Event::KeyDown { key, .. } => (InputSource::Key(key), true),
+            kapp::Event::KeyUp { key, .. } => (InputSource::Key(key), false),
+            kapp::Event::PointerDown { button, .. } => (InputSource::PointerButton(button), true),
+            kapp::Event::PointerUp { button, .. } => (InputSource::PointerButton(button), false),
+            _ => return,
+        };
+
+        if is_down {
+            if let Some(action) = self.rebinding.take() {
+                self.set_bindings(&action, vec![Binding::Button(source)]);
+                self.rebind_press = Some(source);
+                return;
+            }
+            if self.rebind_press == Some(source) {
+                return;
+            }
+            if self.down.insert(source) {
+                self.pressed.insert(source);
+                self.draw_pressed.insert(source);
+            }
+        } else if self.rebind_press == Some(source) {
+            self.rebind_press = None;
+        } else if self.down.remove(&source) {
+            self.released.insert(source);
+            self.draw_released.insert(source);
+        }
+    }
+
+    /// Clears pressed and released edges. Called after each fixed update.
+    pub fn clear(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+    }
+
+    /// Clears the edges seen by [Actions::pressed_this_draw]. Called after each draw.
+    pub fn clear_draw(&mut self) {
+        self.draw_pressed.clear();
+        self.draw_released.clear();
+    }
+
+    /// Writes the bindings in a simple text format with one action per line:
+    ///
+    /// ```text
+    /// jump: Space, Pointer(Primary)
+    /// move: Axis2D(W, S, A, D), Axis2D(Up, Down, Left, Right)
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut actions: Vec<_> = self.bindings.iter().collect();
+        actions.sort_by_key(|(name, _)| *name);
+
+        let mut text = String::new();
+        for (action, bindings) in actions {
+            let bindings: Vec<String> = bindings.iter().map(binding_to_text).collect();
+            text += &format!("{}: {}\n", action, bindings.join(", "));
+        }
+        text
+    }
+
+    /// Replaces the bindings of every action in `text`. Actions not in `text` are unchanged.
+    pub fn load_text(&mut self, text: &str) -> Result<(), ParseBindingsError> {
+        let mut parsed = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: &str| ParseBindingsError {
+                line: index + 1,
+                message: message.into(),
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (action, bindings) = line.split_once(':').ok_or_else(|| error("missing ':'"))?;
+            let bindings = split_bindings(bindings)
+                .map(|b| binding_from_text(b).ok_or_else(|| error(&format!("unknown binding {b}"))))
+                .collect::<Result<Vec<_>, _>>()?;
+            parsed.push((action.trim().to_string(), bindings));
+        }
+
+        for (action, bindings) in parsed {
+            self.bindings.insert(action, bindings);
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_to_file(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.to_text())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_from_file(&mut self, path: &str) -> std::io::Result<()> {
+        let text = std::fs::read_to_string(path)?;
+        self.load_text(&text)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBindingsError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for ParseBindingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseBindingsError {}
+
+/// Splits on commas that aren't inside parentheses.
+fn split_bindings(text: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut parts = Vec::new();
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(text[start..].trim());
+    parts.into_iter().filter(|p| !p.is_empty())
+}
+
+fn source_to_text(source: &InputSource) -> String {
+    match source {
+        InputSource::Key(key) => format!("{:?}", key),
+        InputSource::PointerButton(button) => format!("Pointer({:?})", button),
+    }
+}
+
+fn source_from_text(text: &str) -> Option<InputSource> {
+    let text = text.trim();
+    if let Some(button) = text
+        .strip_prefix("Pointer(")
+        .and_then(|t| t.strip_suffix(')'))
+    {
+        return [
+            PointerButton::Primary,
+            PointerButton::Secondary,
+            PointerButton::Auxillary,
+        ]
+        .into_iter()
+        .find(|b| format!("{:?}", b) == button.trim())
+        .map(InputSource::PointerButton);
+    }
+    key_from_name(text).map(InputSource::Key)
+}
+
+/// Parses a [Key] from its name, like "Space" or "W".
+pub fn key_from_name(name: &str) -> Option<Key> {
+    KEYS.iter().copied().find(|k| format!("{:?}", k) == name)
+}
+
+fn binding_to_text(binding: &Binding) -> String {
+    match binding {
+        Binding::Button(source) => source_to_text(source),
+        Binding::Axis { negative, positive } => format!(
+            "Axis({}, {})",
+            source_to_text(negative),
+            source_to_text(positive)
+        ),
+        Binding::Axis2D {
+            up,
+            down,
+            left,
+            right,
+        } => format!(
+            "Axis2D({}, {}, {}, {})",
+            source_to_text(up),
+            source_to_text(down),
+            source_to_text(left),
+            source_to_text(right)
+        ),
+    }
+}
+
+fn binding_from_text(text: &str) -> Option<Binding> {
+    let arguments = |prefix: &str| -> Option<Vec<InputSource>> {
+        text.strip_prefix(prefix)?
+            .strip_suffix(')')?
+            .split(',')
+            .map(source_from_text)
+            .collect()
+    };
+
+    if let Some(sources) = arguments("Axis2D(") {
+        match sources[..] {
+            [up, down, left, right] => Some(Binding::Axis2D {
+                up,
+                down,
+                left,
+                right,
+            }),
+            _ => None,
+        }
+    } else if let Some(sources) = arguments("Axis(") {
+        match sources[..] {
+            [negative, positive] => Some(Binding::Axis { negative, positive }),
+            _ => None,
+        }
+    } else {
+        source_from_text(text).map(Binding::Button)
+    }
+}
+
+/// Declares [KEYS] along with an exhaustive match over [Key],
+/// so the list fails to compile if `kapp` adds a key that isn't in it.
+macro_rules! keys {
+    ($($key:ident),* $(,)?) => {
+        /// Every [Key], so any key can be loaded from text.
+        const KEYS: &[Key] = &[$(Key::$key),*];
+
+        #[cfg(test)]
+        fn is_listed_key(key: Key) -> bool {
+            match key {
+                $(Key::$key)|* => true,
+            }
+        }
+    };
+}
+
+keys![
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    Escape,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    PrintScreen,
+    ScrollLock,
+    Pause,
+    BackQuote,
+    Minus,
+    Equal,
+    Backspace,
+    Insert,
+    Home,
+    PageUp,
+    PageDown,
+    Delete,
+    End,
+    Tab,
+    LeftBracket,
+    RightBracket,
+    BackSlash,
+    CapsLock,
+    Semicolon,
+    Apostrophe,
+    Return,
+    Comma,
+    Period,
+    Slash,
+    Space,
+    Shift,
+    LeftShift,
+    RightShift,
+    Control,
+    LeftControl,
+    RightControl,
+    Alt,
+    LeftAlt,
+    RightAlt,
+    Meta,
+    Function,
+    Up,
+    Down,
+    Left,
+    Right,
+    NumLock,
+    NumPad0,
+    NumPad1,
+    NumPad2,
+    NumPad3,
+    NumPad4,
+    NumPad5,
+    NumPad6,
+    NumPad7,
+    NumPad8,
+    NumPad9,
+    NumPadDivide,
+    NumPadMultiply,
+    NumPadSubtract,
+    NumPadAdd,
+    NumPadEnter,
+    NumPadPeriod,
+    Unknown,
+];
+
+#[test]
+fn bindings_text_round_trip_test() {
+    let mut actions = Actions::new();
+    actions.bind("jump", Binding::key(Key::Space));
+    actions.bind("jump", Binding::pointer_button(PointerButton::Primary));
+    actions.bind("move", Binding::wasd());
+    actions.bind(
+        "turn",
+        Binding::Axis {
+            negative: InputSource::Key(Key::Q),
+            positive: InputSource::Key(Key::E),
+        },
+    );
+    actions.bind("menu", Binding::key(Key::Return));
+    actions.bind("menu", Binding::key(Key::F1));
+    actions.bind("crouch", Binding::key(Key::LeftControl));
+
+    let mut loaded = Actions::new();
+    loaded.load_text(&actions.to_text()).unwrap();
+    assert_eq!(loaded.bindings("jump"), actions.bindings("jump"));
+    assert_eq!(loaded.bindings("move"), actions.bindings("move"));
+    assert_eq!(loaded.bindings("turn"), actions.bindings("turn"));
+    assert_eq!(loaded.bindings("menu"), actions.bindings("menu"));
+    assert_eq!(loaded.bindings("crouch"), actions.bindings("crouch"));
+    assert!(loaded.load_text("jump: NotAKey").is_err());
+}
+
+#[test]
+fn rebind_consumes_press_test() {
+    let mut actions = Actions::new();
+    actions.bind("jump", Binding::key(Key::Space));
+    actions.rebind_next_input("jump");
+
+    actions.handle_event(&kapp::Event::KeyDown {
+        key: Key::Return,
+        timestamp: Default::default(),
+    });
+    assert_eq!(actions.bindings("jump"), &[Binding::key(Key::Return)]);
+    assert!(!actions.pressed("jump"));
+    actions.handle_event(&kapp::Event::KeyUp {
+        key: Key::Return,
+        timestamp: Default::default(),
+    });
+    assert!(!actions.released("jump"));
+}
+
+#[test]
+fn key_names_round_trip_test() {
+    for key in KEYS {
+        assert!(is_listed_key(*key));
+        assert_eq!(key_from_name(&format!("{:?}", key)), Some(*key));
+    }
+}
@@ -0,0 +1,517 @@
+use kapp::PointerSource;
+use std::collections::HashMap;
+
+/// Thresholds used by the [GestureRecognizer]. Distances are in window pixels.
+#[derive(Clone, Debug)]
+pub struct GestureSettings {
+    /// A touch that moves further than this isn't a tap or long press.
+    pub tap_max_distance: f64,
+    pub tap_max_seconds: f64,
+    /// The most time between two taps for them to count as a double tap.
+    pub double_tap_max_seconds: f64,
+    pub long_press_seconds: f64,
+    pub swipe_min_distance: f64,
+    pub swipe_max_seconds: f64,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        Self {
+            tap_max_distance: 10.0,
+            tap_max_seconds: 0.3,
+            double_tap_max_seconds: 0.3,
+            long_press_seconds: 0.5,
+            swipe_min_distance: 50.0,
+            swipe_max_seconds: 0.5,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    Tap {
+        x: f64,
+        y: f64,
+    },
+    DoubleTap {
+        x: f64,
+        y: f64,
+    },
+    /// Sent once when a touch has been held in place long enough.
+    LongPress {
+        x: f64,
+        y: f64,
+    },
+    Swipe {
+        start: (f64, f64),
+        end: (f64, f64),
+        direction: SwipeDirection,
+    },
+    /// Two fingers moved apart or together. `scale` is greater than 1.0 when moving apart.
+    Pinch {
+        center: (f64, f64),
+        scale: f64,
+    },
+    /// Two fingers rotated around each other. Positive is clockwise on screen.
+    Rotate {
+        center: (f64, f64),
+        radians: f64,
+    },
+}
+
+struct TrackedTouch {
+    start: (f64, f64),
+    position: (f64, f64),
+    start_time: kinstant::Instant,
+    moved_too_far: bool,
+    long_pressed: bool,
+}
+
+/// An on-screen joystick controlled by touch.
+///
+/// A touch that starts within `radius` of `center` controls the joystick until it ends.
+#[derive(Clone, Debug)]
+pub struct VirtualJoystick {
+    pub center: (f64, f64),
+    pub radius: f64,
+    touch_id: Option<usize>,
+    value: (f32, f32),
+}
+
+impl VirtualJoystick {
+    /// Panics if `radius` isn't greater than zero.
+    pub fn new(center: (f64, f64), radius: f64) -> Self {
+        assert!(radius > 0.0, "VirtualJoystick radius must be greater than zero");
+        Self {
+            center,
+            radius,
+            touch_id: None,
+            value: (0.0, 0.0),
+        }
+    }
+
+    /// An (x, y) value with a length of at most 1.0. Positive y is up.
+    pub fn value(&self) -> (f32, f32) {
+        self.value
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.touch_id.is_some()
+    }
+
+    fn update_value(&mut self, (x, y): (f64, f64)) {
+        let (dx, dy) = ((x - self.center.0) / self.radius, (self.center.1 - y) / self.radius);
+        let length = (dx * dx + dy * dy).sqrt().max(1.0);
+        self.value = ((dx / length) as f32, (dy / length) as f32);
+    }
+}
+
+/// Identifies a [VirtualJoystick] added with [GestureRecognizer::add_joystick].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JoystickId(usize);
+
+/// Turns touch pointer events into [Gesture]s.
+///
+/// Gestures accumulate until the end of the next fixed update, like [crate::Input].
+#[derive(Default)]
+pub struct GestureRecognizer {
+    pub settings: GestureSettings,
+    touches: HashMap<usize, TrackedTouch>,
+    last_tap: Option<(kinstant::Instant, (f64, f64))>,
+    /// Set when more than one finger touched, so lifting them doesn't also count as taps.
+    multi_touch: bool,
+    gestures: Vec<Gesture>,
+    joysticks: Vec<VirtualJoystick>,
+}
+
+impl GestureRecognizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn gestures(&self) -> impl Iterator<Item = &Gesture> {
+        self.gestures.iter()
+    }
+
+    pub fn add_joystick(&mut self, joystick: VirtualJoystick) -> JoystickId {
+        self.joysticks.push(joystick);
+        JoystickId(self.joysticks.len() - 1)
+    }
+
+    pub fn joystick(&self, id: JoystickId) -> &VirtualJoystick {
+        &self.joysticks[id.0]
+    }
+
+    pub fn joystick_mut(&mut self, id: JoystickId) -> &mut VirtualJoystick {
+        &mut self.joysticks[id.0]
+    }
+
+    pub fn handle_event(&mut self, event: &kapp::Event) {
+        self.handle_event_at(event, kinstant::Instant::now());
+    }
+
+    fn handle_event_at(&mut self, event: &kapp::Event, now: kinstant::Instant) {
+        match *event {
+            kapp::Event::PointerDown {
+                x,
+                y,
+                source: PointerSource::Touch,
+                id,
+                ..
+            } => self.touch_down(id, (x, y), now),
+            kapp::Event::PointerMoved {
+                x,
+                y,
+                source: PointerSource::Touch,
+                id,
+                ..
+            } => self.touch_moved(id, (x, y)),
+            kapp::Event::PointerUp {
+                x,
+                y,
+                source: PointerSource::Touch,
+                id,
+                ..
+            } => self.touch_up(id, (x, y), now),
+            _ => {}
+        }
+    }
+
+    fn touch_down(&mut self, id: usize, position: (f64, f64), now: kinstant::Instant) {
+        for joystick in &mut self.joysticks {
+            if joystick.touch_id.is_none() && distance(position, joystick.center) < joystick.radius
+            {
+                joystick.touch_id = Some(id);
+                joystick.update_value(position);
+                return;
+            }
+        }
+
+        self.touches.insert(
+            id,
+            TrackedTouch {
+                start: position,
+                position,
+                start_time: now,
+                moved_too_far: false,
+                long_pressed: false,
+            },
+        );
+        if self.touches.len() > 1 {
+            self.multi_touch = true;
+        }
+    }
+
+    fn touch_moved(&mut self, id: usize, position: (f64, f64)) {
+        if let Some(joystick) = self.joysticks.iter_mut().find(|j| j.touch_id == Some(id)) {
+            joystick.update_value(position);
+            return;
+        }
+
+        let previous_pair = self.two_touch_positions();
+        let Some(touch) = self.touches.get_mut(&id) else {
+            return;
+        };
+        touch.position = position;
+        if distance(touch.start, position) > self.settings.tap_max_distance {
+            touch.moved_too_far = true;
+        }
+
+        if let (Some((a0, b0)), Some((a1, b1))) = (previous_pair, self.two_touch_positions()) {
+            let center = ((a1.0 + b1.0) / 2.0, (a1.1 + b1.1) / 2.0);
+            let previous_distance = distance(a0, b0);
+            if previous_distance > 0.0 {
+                self.push_continuous(Gesture::Pinch {
+                    center,
+                    scale: distance(a1, b1) / previous_distance,
+                });
+            }
+            let angle = |a: (f64, f64), b: (f64, f64)| (b.1 - a.1).atan2(b.0 - a.0);
+            let mut radians = angle(a1, b1) - angle(a0, b0);
+            // Keep the smallest rotation when crossing from -PI to PI.
+            if radians > std::f64::consts::PI {
+                radians -= std::f64::consts::TAU;
+            } else if radians < -std::f64::consts::PI {
+                radians += std::f64::consts::TAU;
+            }
+            self.push_continuous(Gesture::Rotate { center, radians });
+        }
+    }
+
+    fn touch_up(&mut self, id: usize, position: (f64, f64), now: kinstant::Instant) {
+        if let Some(joystick) = self.joysticks.iter_mut().find(|j| j.touch_id == Some(id)) {
+            joystick.touch_id = None;
+            joystick.value = (0.0, 0.0);
+            return;
+        }
+
+        let Some(mut touch) = self.touches.remove(&id) else {
+            return;
+        };
+        // The touch may have moved without sending a move event first.
+        if distance(touch.start, position) > self.settings.tap_max_distance {
+            touch.moved_too_far = true;
+        }
+        let multi_touch = self.multi_touch;
+        if self.touches.is_empty() {
+            self.multi_touch = false;
+        }
+        if multi_touch || touch.long_pressed {
+            return;
+        }
+
+        let seconds = (now - touch.start_time).as_secs_f64();
+        let (dx, dy) = (position.0 - touch.start.0, position.1 - touch.start.1);
+
+        if !touch.moved_too_far && seconds <= self.settings.tap_max_seconds {
+            let (x, y) = position;
+            match self.last_tap.take() {
+                Some((time, last_position))
+                    if (now - time).as_secs_f64() <= self.settings.double_tap_max_seconds
+                        && distance(last_position, position) <= self.settings.tap_max_distance =>
+                {
+                    self.gestures.push(Gesture::DoubleTap { x, y })
+                }
+                _ => {
+                    self.gestures.push(Gesture::Tap { x, y });
+                    self.last_tap = Some((now, position));
+                }
+            }
+        } else if (dx * dx + dy * dy).sqrt() >= self.settings.swipe_min_distance
+            && seconds <= self.settings.swipe_max_seconds
+        {
+            let direction = if dx.abs() > dy.abs() {
+                if dx > 0.0 {
+                    SwipeDirection::Right
+                } else {
+                    SwipeDirection::Left
+                }
+            } else if dy > 0.0 {
+                SwipeDirection::Down
+            } else {
+                SwipeDirection::Up
+            };
+            self.gestures.push(Gesture::Swipe {
+                start: touch.start,
+                end: position,
+                direction,
+            });
+        }
+    }
+
+    /// Sends long presses for touches that have been held long enough.
+    /// Called every fixed update because held touches don't send events.
+    pub fn update(&mut self) {
+        self.update_at(kinstant::Instant::now());
+    }
+
+    fn update_at(&mut self, now: kinstant::Instant) {
+        if self.multi_touch {
+            return;
+        }
+        for touch in self.touches.values_mut() {
+            if !touch.moved_too_far
+                && !touch.long_pressed
+                && (now - touch.start_time).as_secs_f64() >= self.settings.long_press_seconds
+            {
+                touch.long_pressed = true;
+                let (x, y) = touch.position;
+                self.gestures.push(Gesture::LongPress { x, y });
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.gestures.clear();
+    }
+
+    fn two_touch_positions(&self) -> Option<((f64, f64), (f64, f64))> {
+        if self.touches.len() != 2 {
+            return None;
+        }
+        // Sort by id so the pair is in a consistent order between events.
+        let mut touches: Vec<_> = self.touches.iter().collect();
+        touches.sort_by_key(|(id, _)| **id);
+        Some((touches[0].1.position, touches[1].1.position))
+    }
+
+    /// Merges with an earlier gesture of the same kind so there's at most one per fixed update.
+    fn push_continuous(&mut self, gesture: Gesture) {
+        for existing in self.gestures.iter_mut() {
+            match (existing, gesture) {
+                (Gesture::Pinch { center, scale }, Gesture::Pinch { center: c, scale: s }) => {
+                    *center = c;
+                    *scale *= s;
+                    return;
+                }
+                (Gesture::Rotate { center, radians }, Gesture::Rotate { center: c, radians: r }) => {
+                    *center = c;
+                    *radians += r;
+                    return;
+                }
+                _ => {}
+            }
+        }
+        self.gestures.push(gesture);
+    }
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+fn touch_event(kind: &str, id: usize, x: f64, y: f64) -> kapp::Event {
+    let timestamp = std::time::Duration::ZERO;
+    let (source, button) = (PointerSource::Touch, kapp::PointerButton::Primary);
+    match kind {
+        "down" => kapp::Event::PointerDown {
+            x,
+            y,
+            source,
+            button,
+            timestamp,
+            id,
+        },
+        "up" => kapp::Event::PointerUp {
+            x,
+            y,
+            source,
+            button,
+            timestamp,
+            id,
+        },
+        _ => kapp::Event::PointerMoved {
+            x,
+            y,
+            source,
+            timestamp,
+            id,
+        },
+    }
+}
+
+/// Sends touch events at times in seconds and returns the gestures they produced.
+#[cfg(test)]
+fn recognize(events: &[(f64, &str, usize, f64, f64)]) -> Vec<Gesture> {
+    let start = kinstant::Instant::now();
+    let mut recognizer = GestureRecognizer::new();
+    for (seconds, kind, id, x, y) in events {
+        let now = start + std::time::Duration::from_secs_f64(*seconds);
+        if *kind == "update" {
+            recognizer.update_at(now);
+        } else {
+            recognizer.handle_event_at(&touch_event(kind, *id, *x, *y), now);
+        }
+    }
+    recognizer.gestures().copied().collect()
+}
+
+#[test]
+fn tap_gesture_test() {
+    let tap = Gesture::Tap { x: 5.0, y: 0.0 };
+    assert_eq!(
+        recognize(&[(0.0, "down", 0, 0.0, 0.0), (0.2, "up", 0, 5.0, 0.0)]),
+        vec![tap]
+    );
+    assert_eq!(
+        recognize(&[
+            (0.0, "down", 0, 0.0, 0.0),
+            (0.1, "up", 0, 5.0, 0.0),
+            (0.2, "down", 0, 5.0, 0.0),
+            (0.3, "up", 0, 5.0, 0.0),
+        ]),
+        vec![tap, Gesture::DoubleTap { x: 5.0, y: 0.0 }]
+    );
+    // Held too long.
+    assert_eq!(
+        recognize(&[(0.0, "down", 0, 0.0, 0.0), (0.4, "up", 0, 0.0, 0.0)]),
+        vec![]
+    );
+    // Moved too far, even though it came back.
+    assert_eq!(
+        recognize(&[
+            (0.0, "down", 0, 0.0, 0.0),
+            (0.1, "moved", 0, 20.0, 0.0),
+            (0.2, "up", 0, 0.0, 0.0),
+        ]),
+        vec![]
+    );
+}
+
+#[test]
+fn long_press_gesture_test() {
+    assert_eq!(
+        recognize(&[
+            (0.0, "down", 0, 1.0, 2.0),
+            (0.4, "update", 0, 0.0, 0.0),
+            (0.6, "update", 0, 0.0, 0.0),
+            (0.7, "update", 0, 0.0, 0.0),
+            (0.8, "up", 0, 1.0, 2.0),
+        ]),
+        vec![Gesture::LongPress { x: 1.0, y: 2.0 }]
+    );
+    assert_eq!(
+        recognize(&[
+            (0.0, "down", 0, 0.0, 0.0),
+            (0.1, "moved", 0, 20.0, 0.0),
+            (0.6, "update", 0, 0.0, 0.0),
+        ]),
+        vec![]
+    );
+}
+
+#[test]
+fn pinch_gesture_test() {
+    let gestures = recognize(&[
+        (0.0, "down", 0, 0.0, 0.0),
+        (0.0, "down", 1, 100.0, 0.0),
+        (0.1, "moved", 1, 200.0, 0.0),
+        (0.3, "up", 0, 0.0, 0.0),
+        (0.3, "up", 1, 200.0, 0.0),
+    ]);
+    // Lifting the fingers doesn't also count as taps.
+    assert_eq!(
+        gestures,
+        vec![
+            Gesture::Pinch {
+                center: (100.0, 0.0),
+                scale: 2.0,
+            },
+            Gesture::Rotate {
+                center: (100.0, 0.0),
+                radians: 0.0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn swipe_gesture_test() {
+    assert_eq!(
+        recognize(&[(0.0, "down", 0, 0.0, 0.0), (0.2, "up", 0, 0.0, -80.0)]),
+        vec![Gesture::Swipe {
+            start: (0.0, 0.0),
+            end: (0.0, -80.0),
+            direction: SwipeDirection::Up,
+        }]
+    );
+    // Too short.
+    assert_eq!(
+        recognize(&[(0.0, "down", 0, 0.0, 0.0), (0.2, "up", 0, 40.0, 0.0)]),
+        vec![]
+    );
+    // Too slow.
+    assert_eq!(
+        recognize(&[(0.0, "down", 0, 0.0, 0.0), (0.6, "up", 0, 80.0, 0.0)]),
+        vec![]
+    );
+}
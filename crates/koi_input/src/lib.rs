@@ -1,16 +1,116 @@
+mod actions;
+pub use actions::*;
+
+mod gestures;
+pub use gestures::*;
+
+mod routing;
+pub use routing::*;
+
+/// Input state for `FixedUpdate` handlers.
+///
+/// Pressed and released edges accumulate until the end of the next fixed update,
+/// so each edge is seen by exactly one fixed update.
 pub type Input = kapp::StateTracker;
 
+/// Input state for `Draw` handlers.
+///
+/// Like [Input] but edges accumulate until the end of the next draw instead,
+/// so UI code in `Draw` sees each edge exactly once regardless of how many fixed updates ran.
+pub struct DrawInput(pub kapp::StateTracker);
+
+impl std::ops::Deref for DrawInput {
+    type Target = kapp::StateTracker;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for DrawInput {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 pub fn initialize_plugin(resources: &mut koi_resources::Resources) {
     let event_handlers = resources.get_mut::<koi_events::EventHandlers>();
-    event_handlers.add_universal_handler(|event, _, resources| {
+    event_handlers.add_universal_handler(|event, world, resources| {
         if let koi_events::Event::KappEvent(event) = event {
-            resources.get_mut::<Input>().handle_event(event);
+            handle_input_event(event, world, resources);
         }
     });
+    event_handlers.add_handler(koi_events::Event::FixedUpdate, |_, _, resources| {
+        resources.get_mut::<GestureRecognizer>().update();
+    });
     event_handlers.add_handler(koi_events::Event::PostFixedUpdate, |_, _, resources| {
         let input = resources.get_mut::<Input>();
         input.clear();
+        resources.get_mut::<Actions>().clear();
+        resources.get_mut::<GestureRecognizer>().clear();
+        resources.get_mut::<InputRouter>().clear();
     });
+    event_handlers.add_handler(koi_events::Event::PostDraw, |_, _, resources| {
+        resources.get_mut::<DrawInput>().clear();
+        resources.get_mut::<Actions>().clear_draw();
+        resources.get_mut::<InputRouter>().clear_draw();
+    });
+
+    add_input_resources(resources);
+}
 
+fn add_input_resources(resources: &mut koi_resources::Resources) {
     resources.add(Input::new());
+    resources.add(DrawInput(kapp::StateTracker::new()));
+    resources.add(Actions::new());
+    resources.add(GestureRecognizer::new());
+    // Plugins initialized earlier may have already added the router and registered consumers.
+    if resources.try_get::<InputRouter>().is_none() {
+        resources.add(InputRouter::new());
+    }
+}
+
+/// Routes an event through the [InputRouter] and applies it to the input state if not consumed.
+fn handle_input_event(
+    event: &kapp::Event,
+    world: &mut koi_ecs::World,
+    resources: &mut koi_resources::Resources,
+) {
+    if InputRouter::route(event, world, resources) {
+        return;
+    }
+    resources.get_mut::<Input>().handle_event(event);
+    resources.get_mut::<DrawInput>().handle_event(event);
+    resources.get_mut::<Actions>().handle_event(event);
+    resources.get_mut::<GestureRecognizer>().handle_event(event);
+}
+
+#[test]
+fn consumed_input_not_applied_test() {
+    let mut world = koi_ecs::World::new();
+    let mut resources = koi_resources::Resources::new();
+    add_input_resources(&mut resources);
+    resources
+        .get_mut::<InputRouter>()
+        .add_consumer(InputPriority::UI, |event, _, resources| {
+            // Consumers can read the router while routing.
+            let _ = resources.get::<InputRouter>().consumed_since_draw();
+            matches!(
+                event,
+                kapp::Event::KeyDown {
+                    key: kapp::Key::A,
+                    ..
+                }
+            )
+        });
+
+    for key in [kapp::Key::A, kapp::Key::B] {
+        let event = kapp::Event::KeyDown {
+            key,
+            timestamp: Default::default(),
+        };
+        handle_input_event(&event, &mut world, &mut resources);
+    }
+    assert!(!resources.get::<Input>().key(kapp::Key::A));
+    assert!(resources.get::<Input>().key(kapp::Key::B));
+    assert!(resources.get::<InputRouter>().consumed_since_fixed_update());
 }
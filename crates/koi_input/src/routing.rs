@@ -0,0 +1,129 @@
+use kapp::{Key, PointerButton};
+use std::collections::HashSet;
+
+/// The order in which [InputRouter] consumers see events. Higher priorities go first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum InputPriority {
+    Gameplay,
+    UI,
+    Debug,
+}
+
+/// Returns true if the event was handled and shouldn't reach lower priority consumers.
+type Consumer =
+    Box<dyn FnMut(&kapp::Event, &mut koi_ecs::World, &mut koi_resources::Resources) -> bool>;
+
+/// Passes input events to consumers in priority order before they reach [crate::Input].
+///
+/// Once a consumer handles an event, lower priority consumers don't see it and it isn't
+/// applied to [crate::Input], [crate::DrawInput], [crate::Actions] or [crate::GestureRecognizer].
+/// So clicking a UI button doesn't also press the pointer button for gameplay code.
+///
+/// Releases are consumed if and only if their press was, so a press that reached
+/// gameplay is always released even if the pointer is over the UI by then.
+#[derive(Default)]
+pub struct InputRouter {
+    consumers: Vec<(InputPriority, Consumer)>,
+    consumed_keys: HashSet<Key>,
+    consumed_pointer_buttons: HashSet<(usize, PointerButton)>,
+    consumed_since_fixed_update: bool,
+    consumed_since_draw: bool,
+}
+
+impl InputRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consumers with the same priority are called in the order they were added.
+    pub fn add_consumer(
+        &mut self,
+        priority: InputPriority,
+        consumer: impl FnMut(&kapp::Event, &mut koi_ecs::World, &mut koi_resources::Resources) -> bool
+            + 'static,
+    ) {
+        self.insert_consumer(priority, Box::new(consumer));
+    }
+
+    fn insert_consumer(&mut self, priority: InputPriority, consumer: Consumer) {
+        let index = self
+            .consumers
+            .iter()
+            .position(|(p, _)| *p < priority)
+            .unwrap_or(self.consumers.len());
+        self.consumers.insert(index, (priority, consumer));
+    }
+
+    /// True if any event was consumed since the end of the last fixed update.
+    /// Useful for ignoring clicks that were meant for the UI.
+    pub fn consumed_since_fixed_update(&self) -> bool {
+        self.consumed_since_fixed_update
+    }
+
+    /// Like [Self::consumed_since_fixed_update] but for `Draw` handlers.
+    pub fn consumed_since_draw(&self) -> bool {
+        self.consumed_since_draw
+    }
+
+    /// Returns true if the event was consumed.
+    ///
+    /// The consumers are taken out of the [InputRouter] while they run, so they can
+    /// still access it through `resources`, and add consumers.
+    pub fn route(
+        event: &kapp::Event,
+        world: &mut koi_ecs::World,
+        resources: &mut koi_resources::Resources,
+    ) -> bool {
+        let mut consumers = std::mem::take(&mut resources.get_mut::<InputRouter>().consumers);
+        let mut consumed = false;
+        for (_, consumer) in consumers.iter_mut() {
+            if consumer(event, world, resources) {
+                consumed = true;
+                break;
+            }
+        }
+
+        let router = resources.get_mut::<InputRouter>();
+        let added = std::mem::replace(&mut router.consumers, consumers);
+        for (priority, consumer) in added {
+            router.insert_consumer(priority, consumer);
+        }
+        router.track_consumed(event, consumed)
+    }
+
+    /// Releases are consumed if and only if their press was.
+    fn track_consumed(&mut self, event: &kapp::Event, mut consumed: bool) -> bool {
+        match *event {
+            kapp::Event::KeyDown { key, .. } => {
+                if consumed {
+                    self.consumed_keys.insert(key);
+                }
+            }
+            kapp::Event::KeyRepeat { key, .. } => consumed = self.consumed_keys.contains(&key),
+            kapp::Event::KeyUp { key, .. } => consumed = self.consumed_keys.remove(&key),
+            kapp::Event::PointerDown { button, id, .. } => {
+                if consumed {
+                    self.consumed_pointer_buttons.insert((id, button));
+                }
+            }
+            kapp::Event::PointerUp { button, id, .. } => {
+                consumed = self.consumed_pointer_buttons.remove(&(id, button))
+            }
+            _ => {}
+        }
+
+        if consumed {
+            self.consumed_since_fixed_update = true;
+            self.consumed_since_draw = true;
+        }
+        consumed
+    }
+
+    pub fn clear(&mut self) {
+        self.consumed_since_fixed_update = false;
+    }
+
+    pub fn clear_draw(&mut self) {
+        self.consumed_since_draw = false;
+    }
+}
@@ -28,39 +28,73 @@ struct TextureLoadState {
     srgb: Option<Handle<koi_renderer::Texture>>,
 }
 
-async fn fetch_buffers(path: &str, gltf: &kgltf::GlTf) -> Vec<Option<Vec<u8>>> {
+async fn fetch_buffers(
+    path: &str,
+    gltf: &kgltf::GlTf,
+) -> Result<Vec<Option<Vec<u8>>>, AssetErrorKind> {
+    use std::io::Read;
+
     let mut buffers = Vec::with_capacity(gltf.buffers.len());
     for buffer in &gltf.buffers {
         buffers.push(if let Some(uri) = &buffer.uri {
             if uri.starts_with("data:") {
-                panic!("THIS IS AN UNSUPPORTED DATA URI");
+                return Err(AssetErrorKind::UnsupportedFormat(
+                    "glTF buffers with data URIs".into(),
+                ));
             }
-            let path = std::path::Path::new(&path).parent().unwrap().join(uri);
-            Some(
-                koi_fetch::fetch_bytes(path.to_str().unwrap())
-                    .await
-                    .unwrap(),
-            )
+            // Only the buffer's declared length is read, so trailing padding is never downloaded.
+            // The length comes from the glTF, so it's checked against the limit and file size
+            // before anything is allocated.
+            let path = koi_fetch::join_relative(path, uri);
+            let options = koi_fetch::FetchOptions::default();
+            if buffer.byte_length > options.max_bytes {
+                return Err(AssetErrorKind::Fetch(koi_fetch::FetchError::TooLarge {
+                    max_bytes: options.max_bytes,
+                }));
+            }
+            let reader = koi_fetch::fetch_reader(&path, &options)
+                .await
+                .map_err(AssetErrorKind::Fetch)?;
+            let capacity = buffer.byte_length.min(reader.total_bytes().unwrap_or(0));
+            let mut bytes = Vec::with_capacity(capacity);
+            reader
+                .take(buffer.byte_length as u64)
+                .read_to_end(&mut bytes)
+                .map_err(|e| AssetErrorKind::Fetch(e.into()))?;
+            if bytes.len() < buffer.byte_length {
+                return Err(AssetErrorKind::Decode(format!(
+                    "glTF buffer {uri} is shorter than its byteLength"
+                )));
+            }
+            Some(bytes)
         } else {
             None
         })
     }
-    buffers
+    Ok(buffers)
+}
+
+fn invalid_gltf() -> AssetErrorKind {
+    AssetErrorKind::Decode("invalid glTF data".into())
 }
 
-pub(crate) async fn load_glb(path: String) -> Option<PrefabLoadResult> {
+fn invalid_accessor(accessor_index: usize) -> AssetErrorKind {
+    AssetErrorKind::Decode(format!("invalid glTF accessor {accessor_index}"))
+}
+
+pub(crate) async fn load_glb(path: String) -> Result<PrefabLoadResult, AssetErrorKind> {
     let bytes = koi_fetch::fetch_bytes(&path)
         .await
-        .unwrap_or_else(|_| panic!("Failed to open file: {}", path));
+        .map_err(AssetErrorKind::Fetch)?;
 
-    let glb = kgltf::GLB::from_bytes(&bytes).ok()?;
+    let glb = kgltf::GLB::from_bytes(&bytes).map_err(|_| invalid_gltf())?;
 
-    let buffers = fetch_buffers(&path, &glb.gltf).await;
+    let buffers = fetch_buffers(&path, &glb.gltf).await?;
 
     let mesh_primitive_data =
         load_mesh_primitive_data(&glb.gltf, glb.binary_data.as_deref(), &buffers).await?;
 
-    Some(super::PrefabLoadResult::GlTf(GlTfLoadResult {
+    Ok(super::PrefabLoadResult::GlTf(GlTfLoadResult {
         path,
         gltf: glb.gltf,
         // TODO: Can this copy be avoided?
@@ -71,19 +105,19 @@ pub(crate) async fn load_glb(path: String) -> Option<PrefabLoadResult> {
 }
 
 // Step 1: Fetch the glTF file off the main thread and ready its data.
-pub(crate) async fn load_gltf(path: String) -> Option<PrefabLoadResult> {
+pub(crate) async fn load_gltf(path: String) -> Result<PrefabLoadResult, AssetErrorKind> {
     let bytes = koi_fetch::fetch_bytes(&path)
         .await
-        .unwrap_or_else(|_| panic!("Failed to open file: {}", path));
+        .map_err(AssetErrorKind::Fetch)?;
 
-    let s = std::str::from_utf8(&bytes).ok()?;
-    let gltf = <kgltf::GlTf as kgltf::FromJson>::from_json(s)?;
+    let s = std::str::from_utf8(&bytes).map_err(|_| invalid_gltf())?;
+    let gltf = <kgltf::GlTf as kgltf::FromJson>::from_json(s).ok_or_else(invalid_gltf)?;
 
-    let buffers = fetch_buffers(&path, &gltf).await;
+    let buffers = fetch_buffers(&path, &gltf).await?;
 
     let mesh_primitive_data = load_mesh_primitive_data(&gltf, None, &buffers).await?;
 
-    Some(super::PrefabLoadResult::GlTf(GlTfLoadResult {
+    Ok(super::PrefabLoadResult::GlTf(GlTfLoadResult {
         path,
         gltf,
         data: None,
@@ -96,7 +130,7 @@ pub(crate) async fn load_gltf(path: String) -> Option<PrefabLoadResult> {
 pub(crate) fn finalize_gltf_load(
     resources: &Resources,
     gltf_load_result: GlTfLoadResult,
-) -> Option<Prefab> {
+) -> Result<Prefab, AssetErrorKind> {
     let mut new_world = koi_ecs::World::new();
 
     let graphics = &mut resources
@@ -118,16 +152,28 @@ pub(crate) fn finalize_gltf_load(
 
     let data = data.as_ref().map(|d| &d[..]);
 
+    // Reload the prefab with hot reloading when its buffers change too.
+    // Textures aren't watched here because their own store reloads them in place.
+    for uri in gltf.buffers.iter().filter_map(|buffer| buffer.uri.as_ref()) {
+        watch_file(&koi_fetch::join_relative(&path, uri));
+    }
+
     for extension in &gltf.extensions_required {
         match extension.as_str() {
             "KHR_materials_unlit" => {}
             "KHR_mesh_quantization" => {}
-            _ => panic!("Unsupported Gltf extension: {}", extension),
+            _ => {
+                return Err(AssetErrorKind::Decode(format!(
+                    "unsupported required glTF extension {extension}"
+                )))
+            }
         }
     }
 
-    let scene = gltf.scene.unwrap();
-    let scene = &gltf.scenes[scene];
+    let scene = gltf
+        .scene
+        .and_then(|scene| gltf.scenes.get(scene))
+        .ok_or_else(|| AssetErrorKind::Decode("glTF has no valid default scene".into()))?;
 
     let mut texture_load_states = vec![
         TextureLoadState {
@@ -140,7 +186,7 @@ pub(crate) fn finalize_gltf_load(
     let gltf_materials: Vec<_> = gltf
         .materials
         .iter()
-        .map(|material| {
+        .map(|material| -> Result<_, AssetErrorKind> {
             let mut new_material = koi_renderer::Material::default();
             if let Some(pbr_metallic_roughness) = &material.pbr_metallic_roughness {
                 let base_color = pbr_metallic_roughness.base_color_factor;
@@ -153,8 +199,10 @@ pub(crate) fn finalize_gltf_load(
                 );
                 new_material.metallicness = pbr_metallic_roughness.metallic_factor;
                 new_material.perceptual_roughness = pbr_metallic_roughness.roughness_factor;
-                new_material.base_color_texture =
-                    pbr_metallic_roughness.base_color_texture.as_ref().map(|t| {
+                new_material.base_color_texture = pbr_metallic_roughness
+                    .base_color_texture
+                    .as_ref()
+                    .map(|t| {
                         get_texture(
                             &gltf,
                             &data,
@@ -165,7 +213,8 @@ pub(crate) fn finalize_gltf_load(
                             true,
                             t.index,
                         )
-                    });
+                    })
+                    .transpose()?;
 
                 new_material.metallic_roughness_texture = pbr_metallic_roughness
                     .metallic_roughness_texture
@@ -181,21 +230,26 @@ pub(crate) fn finalize_gltf_load(
                             false,
                             t.index,
                         )
-                    });
+                    })
+                    .transpose()?;
             }
 
-            new_material.normal_texture = material.normal_texture.as_ref().map(|t| {
-                get_texture(
-                    &gltf,
-                    &data,
-                    &path,
-                    graphics,
-                    &mut textures,
-                    &mut texture_load_states,
-                    false,
-                    t.index,
-                )
-            });
+            new_material.normal_texture = material
+                .normal_texture
+                .as_ref()
+                .map(|t| {
+                    get_texture(
+                        &gltf,
+                        &data,
+                        &path,
+                        graphics,
+                        &mut textures,
+                        &mut texture_load_states,
+                        false,
+                        t.index,
+                    )
+                })
+                .transpose()?;
 
             /*
             // Is this correct?
@@ -254,9 +308,9 @@ pub(crate) fn finalize_gltf_load(
                 new_material.shader = shader;
             };
 
-            materials.add(new_material)
+            Ok(materials.add(new_material))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     let mut mesh_primitives = Vec::with_capacity(mesh_primitive_data.len());
 
@@ -281,7 +335,7 @@ pub(crate) fn finalize_gltf_load(
             &gltf.nodes,
             *node,
             None,
-        )
+        )?;
     }
 
     let mut model_animations = std::collections::HashMap::new();
@@ -295,7 +349,15 @@ pub(crate) fn finalize_gltf_load(
 
         for channel in gltf_animation.channels.iter() {
             if let Some(node) = channel.target.node {
-                let sampler = &gltf_animation.samplers[channel.sampler];
+                let sampler = gltf_animation
+                    .samplers
+                    .get(channel.sampler)
+                    .ok_or_else(|| {
+                        AssetErrorKind::Decode(format!(
+                            "invalid glTF animation sampler {}",
+                            channel.sampler
+                        ))
+                    })?;
 
                 let timestamp_accessor_index = sampler.input;
                 let value_accessor_index = sampler.output;
@@ -306,7 +368,9 @@ pub(crate) fn finalize_gltf_load(
                     }
                     AnimationSamplerInterpolation::Step => koi_animation::animation_curves::step,
                     AnimationSamplerInterpolation::Cubicspline => {
-                        todo!()
+                        return Err(AssetErrorKind::Decode(
+                            "cubic spline glTF animations are not supported".into(),
+                        ))
                     }
                 };
 
@@ -316,19 +380,26 @@ pub(crate) fn finalize_gltf_load(
                     &buffers,
                     timestamp_accessor_index,
                     |v: Vector<f32, 1>| v[0],
-                )?;
+                )
+                .ok_or_else(|| invalid_accessor(timestamp_accessor_index))?;
 
-                let accessor = gltf.accessors.get(value_accessor_index)?;
+                let accessor = gltf
+                    .accessors
+                    .get(value_accessor_index)
+                    .ok_or_else(|| invalid_accessor(value_accessor_index))?;
                 match channel.target.path {
                     AnimationChannelTargetPath::Translation => {
                         let accessor_type = accessor.type_.clone();
 
                         if accessor_type != AccessorType::Vec3 {
-                            println!("Malformed glTF: Translation animation channel does not match accessor");
-                            return None;
+                            return Err(AssetErrorKind::Decode(
+                                "glTF translation animation channel does not match its accessor"
+                                    .into(),
+                            ));
                         }
                         let translations =
-                            get_buffer(&gltf, &data, &buffers, value_accessor_index, |v| v)?;
+                            get_buffer(&gltf, &data, &buffers, value_accessor_index, |v| v)
+                                .ok_or_else(|| invalid_accessor(value_accessor_index))?;
                         animation_clips.push(koi_animation::AnimationClip {
                             animation_curve,
                             entity_mapping_index: associated_entities.len(),
@@ -345,13 +416,16 @@ pub(crate) fn finalize_gltf_load(
                         let accessor_type = accessor.type_.clone();
 
                         if accessor_type != AccessorType::Vec4 {
-                            println!("Malformed glTF: Rotation animation channel does not match accessor");
-                            return None;
+                            return Err(AssetErrorKind::Decode(
+                                "glTF rotation animation channel does not match its accessor"
+                                    .into(),
+                            ));
                         }
                         let rotations =
                             get_buffer(&gltf, &data, &buffers, value_accessor_index, |v| {
                                 Quaternion(v)
-                            })?;
+                            })
+                            .ok_or_else(|| invalid_accessor(value_accessor_index))?;
                         animation_clips.push(koi_animation::AnimationClip {
                             animation_curve,
                             entity_mapping_index: associated_entities.len(),
@@ -368,13 +442,13 @@ pub(crate) fn finalize_gltf_load(
                         let accessor_type = accessor.type_.clone();
 
                         if accessor_type != AccessorType::Vec3 {
-                            println!(
-                                "Malformed glTF: Scale animation channel does not match accessor"
-                            );
-                            return None;
+                            return Err(AssetErrorKind::Decode(
+                                "glTF scale animation channel does not match its accessor".into(),
+                            ));
                         }
                         let scales =
-                            get_buffer(&gltf, &data, &buffers, value_accessor_index, |v| v)?;
+                            get_buffer(&gltf, &data, &buffers, value_accessor_index, |v| v)
+                                .ok_or_else(|| invalid_accessor(value_accessor_index))?;
                         animation_clips.push(koi_animation::AnimationClip {
                             animation_curve,
                             entity_mapping_index: associated_entities.len(),
@@ -387,9 +461,19 @@ pub(crate) fn finalize_gltf_load(
                             }),
                         });
                     }
-                    AnimationChannelTargetPath::Weights => todo!(),
+                    AnimationChannelTargetPath::Weights => {
+                        return Err(AssetErrorKind::Decode(
+                            "glTF morph target weight animations are not supported".into(),
+                        ))
+                    }
                 }
-                associated_entities.push(node_index_to_entity[node].map(|e| e.0));
+                associated_entities.push(
+                    node_index_to_entity
+                        .get(node)
+                        .copied()
+                        .flatten()
+                        .map(|e| e.0),
+                );
             }
         }
 
@@ -418,7 +502,7 @@ pub(crate) fn finalize_gltf_load(
         },));
     }
 
-    Some(Prefab(new_world))
+    Ok(Prefab(new_world))
 }
 
 // This helper function is used to load-textures late.
@@ -433,52 +517,69 @@ fn get_texture(
     texture_load_states: &mut [TextureLoadState],
     srgb: bool,
     texture_index: usize,
-) -> Handle<koi_renderer::Texture> {
-    let image_index = gltf.textures[texture_index].source.unwrap();
+) -> Result<Handle<koi_renderer::Texture>, AssetErrorKind> {
+    let invalid_texture =
+        || AssetErrorKind::Decode(format!("invalid glTF texture {texture_index}"));
+
+    let image_index = gltf
+        .textures
+        .get(texture_index)
+        .and_then(|texture| texture.source)
+        .ok_or_else(invalid_texture)?;
     if srgb {
         if let Some(handle) = texture_load_states[texture_index].srgb.clone() {
-            return handle;
+            return Ok(handle);
         }
     } else if let Some(handle) = texture_load_states[texture_index].linear.clone() {
-        return handle;
+        return Ok(handle);
     }
 
-    let image = &gltf.images[image_index];
+    let image = gltf.images.get(image_index).ok_or_else(invalid_texture)?;
     let new_handle = if let Some(uri) = &image.uri {
-        let path = std::path::Path::new(path).parent().unwrap().join(uri);
-
+        let path = koi_fetch::join_relative(path, uri);
         textures.load(
-            path.to_str().unwrap(),
+            &path,
             koi_renderer::koi_graphics_context::TextureSettings {
                 srgb,
                 ..Default::default()
             },
         )
     } else {
-        let buffer_view = &gltf.buffer_views[image.buffer_view.unwrap()];
-
-        let bytes = &data.unwrap()
-            [buffer_view.byte_offset..buffer_view.byte_offset + buffer_view.byte_length];
-        let extension = match image.mime_type.as_ref().unwrap() {
+        let buffer_view = image
+            .buffer_view
+            .and_then(|buffer_view| gltf.buffer_views.get(buffer_view))
+            .ok_or_else(invalid_texture)?;
+
+        let bytes = data
+            .and_then(|data| {
+                let end = buffer_view
+                    .byte_offset
+                    .checked_add(buffer_view.byte_length)?;
+                data.get(buffer_view.byte_offset..end)
+            })
+            .ok_or_else(invalid_texture)?;
+        let extension = match image.mime_type.as_ref().ok_or_else(invalid_texture)? {
             kgltf::ImageMimeType::ImageJpeg => "jpeg",
             kgltf::ImageMimeType::ImagePng => "png",
         };
 
         // TODO: This should be decoded off the main thread.
 
-        textures.add(
-            new_texture_from_bytes(
-                graphics,
-                extension,
-                bytes,
-                koi_graphics_context::TextureSettings {
-                    srgb,
-                    ..Default::default()
-                },
-            )
-            .unwrap()
-            .0,
-        )
+        match new_texture_from_bytes(
+            graphics,
+            extension,
+            bytes,
+            koi_graphics_context::TextureSettings {
+                srgb,
+                ..Default::default()
+            },
+        ) {
+            Some((texture, _)) => textures.add(texture),
+            None => {
+                klog::log!("Failed to decode embedded texture {texture_index} in {path}");
+                koi_renderer::Texture::ERROR
+            }
+        }
     };
 
     if srgb {
@@ -486,7 +587,7 @@ fn get_texture(
     } else {
         texture_load_states[texture_index].linear = Some(new_handle.clone())
     }
-    new_handle
+    Ok(new_handle)
 }
 
 fn initialize_nodes(
@@ -498,8 +599,10 @@ fn initialize_nodes(
     nodes: &[kgltf::Node],
     node_index: usize,
     parent: Option<Entity>,
-) {
-    let node = &nodes[node_index];
+) -> Result<(), AssetErrorKind> {
+    let node = nodes
+        .get(node_index)
+        .ok_or_else(|| AssetErrorKind::Decode(format!("invalid glTF node {node_index}")))?;
     let transform: Transform = if let Some(matrix) = &node.matrix {
         Transform::from_mat4(matrix.try_into().unwrap())
     } else {
@@ -511,12 +614,19 @@ fn initialize_nodes(
     };
 
     let entity = if let Some(mesh) = node.mesh {
-        let mesh_primitives = &mesh_primitives[mesh];
+        let mesh_primitives = mesh_primitives
+            .get(mesh)
+            .ok_or_else(|| AssetErrorKind::Decode(format!("invalid glTF mesh {mesh}")))?;
 
         let entity_root = gltf_world.spawn((transform,));
         for (mesh, material_index) in mesh_primitives {
-            let material_handle =
-                material_index.map_or_else(|| Handle::PLACEHOLDER, |i| gltf_materials[i].clone());
+            let material_handle = match material_index {
+                Some(i) => gltf_materials
+                    .get(*i)
+                    .cloned()
+                    .ok_or_else(|| AssetErrorKind::Decode(format!("invalid glTF material {i}")))?,
+                None => Handle::PLACEHOLDER,
+            };
             let primitive_entity =
                 gltf_world.spawn((mesh.clone(), material_handle, Transform::new()));
             gltf_world
@@ -528,19 +638,22 @@ fn initialize_nodes(
         gltf_world.spawn((transform,))
     };
 
-    /*
     if let Some(name) = &node.name {
         gltf_world
-            .add_component(entity, Name(name.clone()))
+            .insert_one(entity, koi_ecs::Name(name.clone()))
             .unwrap();
     }
-    */
 
     if let Some(parent) = parent {
         gltf_world.set_parent(parent, entity).unwrap();
     }
 
     node_index_to_entity.resize(node_index_to_entity.len().max(node_index + 1), None);
+    if node_index_to_entity[node_index].is_some() {
+        return Err(AssetErrorKind::Decode(format!(
+            "glTF node {node_index} appears more than once in the scene"
+        )));
+    }
     node_index_to_entity[node_index] = Some((entity, transform));
 
     for child in &node.children {
@@ -553,15 +666,16 @@ fn initialize_nodes(
             nodes,
             *child,
             Some(entity),
-        );
+        )?;
     }
+    Ok(())
 }
 
 pub(super) async fn load_mesh_primitive_data(
     gltf: &kgltf::GlTf,
     data: Option<&[u8]>,
     buffers: &[Option<Vec<u8>>],
-) -> Option<Vec<MeshPrimitiveData>> {
+) -> Result<Vec<MeshPrimitiveData>, AssetErrorKind> {
     let mut meshes = Vec::with_capacity(gltf.meshes.len());
     for mesh in &gltf.meshes {
         let mut primitives = Vec::with_capacity(mesh.primitives.len());
@@ -579,32 +693,46 @@ pub(super) async fn load_mesh_primitive_data(
                 // https://www.khronos.org/registry/glTF/specs/2.0/glTF-2.0.html#meshes-overview
                 match attribute.as_str() {
                     "POSITION" => {
-                        positions = Some(get_buffer(gltf, &data, buffers, *accessor_index, |v| v)?);
+                        positions = Some(
+                            get_buffer(gltf, &data, buffers, *accessor_index, |v| v)
+                                .ok_or_else(|| invalid_accessor(*accessor_index))?,
+                        );
                     }
                     "TEXCOORD_0" => {
-                        texture_coordinates =
-                            Some(get_buffer(gltf, &data, buffers, *accessor_index, |v| v)?);
+                        texture_coordinates = Some(
+                            get_buffer(gltf, &data, buffers, *accessor_index, |v| v)
+                                .ok_or_else(|| invalid_accessor(*accessor_index))?,
+                        );
                     }
                     "NORMAL" => {
-                        normals = Some(get_buffer(gltf, &data, buffers, *accessor_index, |v| v)?);
+                        normals = Some(
+                            get_buffer(gltf, &data, buffers, *accessor_index, |v| v)
+                                .ok_or_else(|| invalid_accessor(*accessor_index))?,
+                        );
                     }
                     "COLOR_0" => {
-                        let accessor = &gltf.accessors[*accessor_index];
+                        let accessor = gltf
+                            .accessors
+                            .get(*accessor_index)
+                            .ok_or_else(|| invalid_accessor(*accessor_index))?;
                         let accessor_type = accessor.type_.clone();
 
                         // COLOR_0 can be different accessor types according to the spec.
                         // Here we make them always a `Vec4`
                         match accessor_type {
                             kgltf::AccessorType::Vec4 => {
-                                colors =
-                                    Some(get_buffer(gltf, &data, buffers, *accessor_index, |v| v)?);
+                                colors = Some(
+                                    get_buffer(gltf, &data, buffers, *accessor_index, |v| v)
+                                        .ok_or_else(|| invalid_accessor(*accessor_index))?,
+                                );
                             }
                             kgltf::AccessorType::Vec3 => {
                                 let colors_vec3: Vec<Vec3> =
-                                    get_buffer(gltf, &data, buffers, *accessor_index, |v| v)?;
+                                    get_buffer(gltf, &data, buffers, *accessor_index, |v| v)
+                                        .ok_or_else(|| invalid_accessor(*accessor_index))?;
                                 colors = Some(colors_vec3.iter().map(|v| v.extend(1.0)).collect());
                             }
-                            _ => unimplemented!(),
+                            _ => return Err(invalid_accessor(*accessor_index)),
                         }
                     }
                     "TANGENT" => {}
@@ -616,22 +744,26 @@ pub(super) async fn load_mesh_primitive_data(
             }
 
             if let Some(indices) = primitive.indices {
-                let indices = get_indices(gltf, &data, buffers, indices).await?;
-
-                for index in indices.iter() {
-                    for i in index {
-                        if *i >= positions.as_ref().unwrap().len() as u32 {
-                            println!(
-                                "Primitive references out of bound indices: {:?}",
-                                (*i, positions.as_ref().unwrap().len())
-                            );
-                            panic!()
-                        }
-                    }
+                let indices = get_indices(gltf, &data, buffers, indices)
+                    .await
+                    .ok_or_else(|| invalid_accessor(indices))?;
+
+                let positions = positions.ok_or_else(|| {
+                    AssetErrorKind::Decode("glTF primitive has no positions".into())
+                })?;
+                if let Some(i) = indices
+                    .iter()
+                    .flatten()
+                    .find(|i| **i as usize >= positions.len())
+                {
+                    return Err(AssetErrorKind::Decode(format!(
+                        "glTF primitive index {i} is out of bounds for {} positions",
+                        positions.len()
+                    )));
                 }
 
                 let mesh_data = koi_renderer::MeshData {
-                    positions: positions.unwrap(),
+                    positions,
                     normals: normals.unwrap_or_default(),
                     texture_coordinates: texture_coordinates.unwrap_or_default(),
                     colors: colors.unwrap_or_default(),
@@ -645,7 +777,7 @@ pub(super) async fn load_mesh_primitive_data(
         }
         meshes.push(MeshPrimitiveData { primitives });
     }
-    Some(meshes)
+    Ok(meshes)
 }
 
 fn read_accessor_bytes<'a>(
@@ -655,16 +787,9 @@ fn read_accessor_bytes<'a>(
     accessor_index: usize,
 ) -> Option<(&'a [u8], &'a Accessor, usize)> {
     let accessor = gltf.accessors.get(accessor_index)?;
-    let buffer_view = gltf.buffer_views.get(accessor.buffer_view.unwrap())?;
+    let buffer_view = gltf.buffer_views.get(accessor.buffer_view?)?;
 
-    let member_size = match accessor.component_type {
-        AccessorComponentType::Byte => std::mem::size_of::<i8>(),
-        AccessorComponentType::UnsignedByte => std::mem::size_of::<u8>(),
-        AccessorComponentType::Short => std::mem::size_of::<i16>(),
-        AccessorComponentType::UnsignedShort => std::mem::size_of::<u16>(),
-        AccessorComponentType::UnsignedInt => std::mem::size_of::<u32>(),
-        AccessorComponentType::Float => std::mem::size_of::<f32>(),
-    };
+    let member_size = component_size(&accessor.component_type);
 
     let items_per_member = match accessor.type_ {
         kgltf::AccessorType::Scalar => 1,
@@ -679,17 +804,20 @@ fn read_accessor_bytes<'a>(
     let stride = buffer_view
         .byte_stride
         .unwrap_or(member_size * items_per_member);
-    let len_bytes = accessor.count * stride;
+    if stride < member_size * items_per_member {
+        return None;
+    }
+    let len_bytes = accessor.count.checked_mul(stride)?;
 
-    let start = buffer_view.byte_offset + accessor.byte_offset;
-    let end = start + len_bytes;
+    let start = buffer_view.byte_offset.checked_add(accessor.byte_offset)?;
+    let end = start.checked_add(len_bytes)?;
 
     let buffer = gltf.buffers.get(buffer_view.buffer)?;
     Some((
         if buffer.uri.is_some() {
             buffers.get(buffer_view.buffer)?.as_ref()?.get(start..end)?
         } else {
-            data.as_ref().unwrap().get(start..end)?
+            data.as_ref()?.get(start..end)?
         },
         accessor,
         stride,
@@ -727,7 +855,7 @@ async fn get_indices(
                     .map(|u| [u[0] as u32, u[1] as u32, u[2] as u32])
                     .collect()
             }
-            _ => unreachable!(), // Should error instead
+            _ => return None,
         })
     }
 }
@@ -739,8 +867,10 @@ fn get_buffer<OUT, const DIM: usize>(
     accessor_index: usize,
     map: fn(Vector<f32, DIM>) -> OUT,
 ) -> Option<Vec<OUT>> {
-    let (bytes, accessor, stride) =
-        read_accessor_bytes(gltf, data, buffers, accessor_index).unwrap();
+    let (bytes, accessor, stride) = read_accessor_bytes(gltf, data, buffers, accessor_index)?;
+    if stride < DIM * component_size(&accessor.component_type) {
+        return None;
+    }
 
     Some(if accessor.normalized {
         match accessor.component_type {
@@ -809,6 +939,17 @@ fn get_buffer<OUT, const DIM: usize>(
     })
 }
 
+fn component_size(component_type: &AccessorComponentType) -> usize {
+    match component_type {
+        AccessorComponentType::Byte => std::mem::size_of::<i8>(),
+        AccessorComponentType::UnsignedByte => std::mem::size_of::<u8>(),
+        AccessorComponentType::Short => std::mem::size_of::<i16>(),
+        AccessorComponentType::UnsignedShort => std::mem::size_of::<u16>(),
+        AccessorComponentType::UnsignedInt => std::mem::size_of::<u32>(),
+        AccessorComponentType::Float => std::mem::size_of::<f32>(),
+    }
+}
+
 trait FromLeBytes {
     fn from_le_bytes(bytes: &[u8]) -> Self;
 }
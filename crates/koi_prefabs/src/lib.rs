@@ -48,11 +48,14 @@ impl koi_assets::AssetTrait for Prefab {
     type Settings = ();
 }
 
-async fn load_world(path: String, _settings: ()) -> Option<PrefabLoadResult> {
+async fn load_world(
+    path: String,
+    _settings: (),
+) -> Result<PrefabLoadResult, koi_assets::AssetErrorKind> {
     let extension = std::path::Path::new(&path)
         .extension()
         .and_then(std::ffi::OsStr::to_str)
-        .expect("Expected file extension for prefab")
+        .unwrap_or_default()
         .to_lowercase();
 
     match &*extension {
@@ -60,12 +63,9 @@ async fn load_world(path: String, _settings: ()) -> Option<PrefabLoadResult> {
         "gltf" => gltf::load_gltf(path).await,
         #[cfg(feature = "gltf")]
         "glb" => gltf::load_glb(path).await,
-        _ => {
-            println!(
-                "Error loading prefab. Unsupported file extension: {extension} for path {path}"
-            );
-            None
-        }
+        _ => Err(koi_assets::AssetErrorKind::UnsupportedFormat(format!(
+            "prefab file extension \"{extension}\""
+        ))),
     }
 }
 
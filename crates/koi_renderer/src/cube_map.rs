@@ -1,6 +1,6 @@
 use crate::{spherical_harmonics::SphericalHarmonics, Renderer};
 use kmath::*;
-use koi_assets::AssetTrait;
+use koi_assets::{AssetErrorKind, AssetTrait};
 use koi_resources::Resources;
 
 pub struct CubeMap {
@@ -19,7 +19,7 @@ pub struct CubeMapResult {
     size: usize,
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Default, PartialEq)]
 pub struct CubeMapSettings {
     pub luminance_of_brightest_pixel: Option<f32>,
 }
@@ -35,15 +35,22 @@ pub mod luminance {
 
 impl AssetTrait for CubeMap {
     type Settings = CubeMapSettings;
+
+    fn byte_size(&self) -> Option<usize> {
+        Some(self.face_size * self.face_size * 6 * self.texture.pixel_format().bytes_per_pixel())
+    }
 }
 
 pub fn initialize_cube_maps(resources: &mut Resources) {
     #[allow(unused)]
-    async fn load(path: String, settings: CubeMapSettings) -> Option<crate::CubeMapResult> {
+    async fn load(
+        path: String,
+        settings: CubeMapSettings,
+    ) -> Result<crate::CubeMapResult, AssetErrorKind> {
         let extension: String = std::path::Path::new(&path)
             .extension()
             .and_then(std::ffi::OsStr::to_str)
-            .expect("Expected cube map file extension")
+            .unwrap_or_default()
             .to_lowercase();
 
         let data: crate::TextureResult = match &*extension {
@@ -51,17 +58,17 @@ pub fn initialize_cube_maps(resources: &mut Resources) {
             "hdr" => {
                 let bytes = koi_fetch::fetch_bytes(&path)
                     .await
-                    .unwrap_or_else(|_| panic!("Failed to open file: {}", path));
-                hdri_data_from_bytes(&bytes)?
+                    .map_err(AssetErrorKind::Fetch)?;
+                hdri_data_from_bytes(&bytes)
+                    .ok_or_else(|| AssetErrorKind::Decode("invalid HDR image".into()))?
             }
             _ => {
-                println!(
-                    "Error loading image. Unsupported file extension: {extension} for path {path}"
-                );
-                return None;
+                return Err(AssetErrorKind::UnsupportedFormat(format!(
+                    "cube map file extension \"{extension}\""
+                )));
             }
         };
-        Some(match data.data {
+        Ok(match data.data {
             crate::TextureData::Bytes(b) => {
                 let bytes = b.as_u8_array();
                 prepare_cubemap(bytes, data.width, data.height, settings)
@@ -75,8 +82,8 @@ pub fn initialize_cube_maps(resources: &mut Resources) {
         result: crate::CubeMapResult,
         _settings: CubeMapSettings,
         resources: &Resources,
-    ) -> Option<CubeMap> {
-        Some(finalize_cube_map(resources, result))
+    ) -> Result<CubeMap, AssetErrorKind> {
+        Ok(finalize_cube_map(resources, result))
     }
 
     let placeholder = finalize_cube_map(
@@ -4,8 +4,9 @@ use crate::{
 };
 
 use koi_assets::*;
+use koi_ecs::ActiveExtension;
 use koi_resources::Resources;
-use koi_transform::{transform_plugin::update_global_transforms, GlobalTransform};
+use koi_transform::{transform_plugin::refresh_global_transforms, GlobalTransform};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 /// Used to configure which layers [Entity]s will render on.
@@ -50,8 +51,8 @@ impl Default for InitialSettings {
 
 pub async fn initialize_plugin(resources: &mut Resources) {
     let world_cloner = resources.get_mut::<koi_ecs::WorldCloner>();
-    world_cloner.register_clone_type::<Handle<Material>>();
-    world_cloner.register_clone_type::<Handle<Mesh>>();
+    world_cloner.register_diffable_type::<Handle<Material>>();
+    world_cloner.register_diffable_type::<Handle<Mesh>>();
 
     let initial_settings = resources.remove::<InitialSettings>().unwrap_or_default();
 
@@ -146,12 +147,15 @@ fn draw_inner(world: &mut koi_ecs::World, resources: &mut Resources, base_viewpo
         //     .resize(&*window, window_width, window_height);
 
         let light_probe = world
-            .query::<&LightProbe>()
+            .query::<koi_ecs::Without<&LightProbe, &koi_ecs::InactiveInHierarchy>>()
             .iter()
             .next()
             .map(|v| v.1.clone());
 
-        let mut camera_query = world.query::<(&GlobalTransform, &Camera, Option<&RenderFlags>)>();
+        let mut camera_query = world.query::<koi_ecs::Without<
+            (&GlobalTransform, &Camera, Option<&RenderFlags>),
+            &koi_ecs::InactiveInHierarchy,
+        >>();
 
         // TODO: Avoid this allocation
         let mut cameras = Vec::new();
@@ -173,33 +177,45 @@ fn draw_inner(world: &mut koi_ecs::World, resources: &mut Resources, base_viewpo
 
             render_pass.set_light_probe(light_probe.as_ref());
 
-            let mut directional_lights =
-                world.query::<koi_ecs::Without<(&DirectionalLight, &GlobalTransform), &Camera>>();
+            let mut directional_lights = world.query::<koi_ecs::Without<
+                koi_ecs::Without<(&DirectionalLight, &GlobalTransform), &Camera>,
+                &koi_ecs::InactiveInHierarchy,
+            >>();
 
             for (_, (light, light_transform)) in directional_lights.iter() {
                 render_pass.add_directional_light(light_transform, light)
             }
 
-            let mut point_lights =
-                world.query::<koi_ecs::Without<(&PointLight, &GlobalTransform), &Camera>>();
+            let mut point_lights = world.query::<koi_ecs::Without<
+                koi_ecs::Without<(&PointLight, &GlobalTransform), &Camera>,
+                &koi_ecs::InactiveInHierarchy,
+            >>();
 
             for (_, (light, light_transform)) in point_lights.iter() {
                 render_pass.add_point_light(light_transform, light)
             }
 
-            let mut renderables = world.query::<(
-                &Handle<Mesh>,
-                &Handle<Material>,
-                &GlobalTransform,
-                Option<&RenderFlags>,
-                Option<&Color>,
-            )>();
+            let mut renderables = world.query::<koi_ecs::Without<
+                (
+                    &Handle<Mesh>,
+                    &Handle<Material>,
+                    &GlobalTransform,
+                    Option<&RenderFlags>,
+                    Option<&Color>,
+                ),
+                &koi_ecs::InactiveInHierarchy,
+            >>();
 
             for (_, (gpu_mesh, material, transform, render_flags, color)) in renderables.iter() {
                 let render_flags = render_flags.unwrap_or(&RenderFlags::DEFAULT);
 
                 if camera_render_flags.includes_layer(*render_flags) {
-                    render_pass.draw_mesh(gpu_mesh, material, transform, color.cloned());
+                    render_pass.draw_mesh_with_matrix(
+                        gpu_mesh,
+                        material,
+                        transform.local_to_world(),
+                        color.cloned(),
+                    );
                 }
             }
 
@@ -208,14 +224,9 @@ fn draw_inner(world: &mut koi_ecs::World, resources: &mut Resources, base_viewpo
     }
 
     for (_, other_scene_draw) in world.query::<&mut WorldToDrawInViewport>().iter() {
-        // The event isn't used.
         // This makes sure everything has the global transforms
-
-        update_global_transforms(
-            &koi_events::Event::Draw,
-            &mut other_scene_draw.scene,
-            resources,
-        );
+        other_scene_draw.scene.update_active_hierarchy();
+        refresh_global_transforms(&mut other_scene_draw.scene, resources);
 
         println!("DRAW SUB VIEWPORT!");
         draw_inner(
@@ -228,6 +239,9 @@ fn draw_inner(world: &mut koi_ecs::World, resources: &mut Resources, base_viewpo
 }
 pub fn draw(_: &koi_events::Event, world: &mut koi_ecs::World, resources: &mut Resources) {
     let now = std::time::Instant::now();
+    // Entities moved or deactivated in `Draw` handlers, like cameras, are rendered this frame.
+    world.update_active_hierarchy();
+    refresh_global_transforms(world, resources);
     draw_inner(
         world,
         resources,
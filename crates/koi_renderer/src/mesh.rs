@@ -42,6 +42,16 @@ pub struct MeshData {
 }
 
 impl MeshData {
+    /// How much memory the mesh's attributes and indices use.
+    pub fn byte_size(&self) -> usize {
+        use std::mem::size_of_val;
+        size_of_val(self.positions.as_slice())
+            + size_of_val(self.indices.as_slice())
+            + size_of_val(self.normals.as_slice())
+            + size_of_val(self.texture_coordinates.as_slice())
+            + size_of_val(self.colors.as_slice())
+    }
+
     pub fn apply_transform(&mut self, transform: koi_transform::Transform) {
         let transform_matrix = transform.local_to_world();
         for position in self.positions.iter_mut() {
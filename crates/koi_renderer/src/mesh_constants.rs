@@ -20,6 +20,16 @@ impl Mesh {
 
 impl AssetTrait for Mesh {
     type Settings = ();
+
+    /// The size of the [MeshData], counted again if it's also uploaded to the GPU.
+    fn byte_size(&self) -> Option<usize> {
+        let bytes = self.mesh_data.as_ref()?.byte_size();
+        Some(if self.gpu_mesh.is_some() {
+            bytes * 2
+        } else {
+            bytes
+        })
+    }
 }
 
 pub fn initialize_meshes(
@@ -184,12 +184,24 @@ impl RenderPass {
         material: &Handle<Material>,
         transform: &Transform,
         color: Option<Color>,
+    ) {
+        self.draw_mesh_with_matrix(mesh, material, transform.local_to_world(), color)
+    }
+
+    /// Like [Self::draw_mesh] but with a matrix that may include shear,
+    /// like [koi_transform::GlobalTransform::local_to_world].
+    pub fn draw_mesh_with_matrix(
+        &mut self,
+        mesh: &Handle<Mesh>,
+        material: &Handle<Material>,
+        local_to_world: kmath::Mat4,
+        color: Option<Color>,
     ) {
         // Todo: Immediately cull mesh if outside frustum bounds.
         self.meshes_to_draw.push((
             material.clone(),
             mesh.clone(),
-            transform.local_to_world(),
+            local_to_world,
             color.map_or(kmath::Vec4::fill(1.0), |c| c.to_rgb_color(self.color_space)),
         ))
     }
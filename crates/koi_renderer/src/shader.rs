@@ -83,7 +83,7 @@ pub(crate) struct ShaderRenderProperties {
     // pub(crate) p_cube_map: koi_graphics_context::CubeMapProperty,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 pub struct ShaderSettings {
     pub faces_to_render: koi_graphics_context::FacesToRender,
     pub blending: Option<(
@@ -1,5 +1,5 @@
 use crate::{Shader, ShaderSettings};
-use koi_assets::Handle;
+use koi_assets::{AssetErrorKind, Handle};
 use koi_graphics_context::BlendFactor;
 use koi_resources::Resources;
 
@@ -39,28 +39,27 @@ pub fn initialize_shaders(renderer: &mut crate::Renderer, resources: &mut Resour
         )
         .unwrap();
 
-    async fn load_shader(path: String, _settings: ShaderSettings) -> Option<String> {
+    async fn load_shader(
+        path: String,
+        _settings: ShaderSettings,
+    ) -> Result<String, AssetErrorKind> {
         let bytes = koi_fetch::fetch_bytes(&path)
             .await
-            .unwrap_or_else(|_| panic!("Failed to open file: {}", path));
+            .map_err(AssetErrorKind::Fetch)?;
 
-        Some(core::str::from_utf8(&bytes).ok()?.to_owned())
+        Ok(core::str::from_utf8(&bytes)
+            .map_err(|_| AssetErrorKind::Decode("shader source is not valid UTF-8".into()))?
+            .to_owned())
     }
     fn finalize_shader_load(
         source: String,
         settings: ShaderSettings,
         resources: &Resources,
-    ) -> Option<Shader> {
-        let result = resources
+    ) -> Result<Shader, AssetErrorKind> {
+        resources
             .get::<crate::Renderer>()
-            .new_shader(&source, settings);
-        match result {
-            Ok(s) => Some(s),
-            Err(e) => {
-                println!("Shader compilation error: {:#?}", e);
-                None
-            }
-        }
+            .new_shader(&source, settings)
+            .map_err(|e| AssetErrorKind::Finalize(format!("shader compilation error: {:#?}", e)))
     }
 
     let mut asset_store =
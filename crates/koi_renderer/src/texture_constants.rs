@@ -10,10 +10,17 @@ impl Texture {
     /// A texture that produces normals that all face outwards.
     /// The color is (0.5, 0.5, 1.0)
     pub const DEFAULT_NORMAL: Handle<Texture> = Handle::<Texture>::from_index(2);
+
+    /// A magenta texture used in place of textures that failed to load.
+    pub const ERROR: Handle<Texture> = Handle::<Texture>::from_index(3);
 }
 
 impl AssetTrait for Texture {
     type Settings = koi_graphics_context::TextureSettings;
+
+    fn byte_size(&self) -> Option<usize> {
+        Some(self.0.byte_size())
+    }
 }
 
 pub(crate) struct TextureResult {
@@ -36,7 +43,8 @@ pub fn new_texture_from_bytes(
     bytes: &[u8],
     settings: TextureSettings,
 ) -> Option<(Texture, TextureInfo)> {
-    let texture_result = texture_result_from_extension_and_bytes(extension, bytes, settings)?;
+    let texture_result =
+        texture_result_from_extension_and_bytes(extension, bytes, settings).ok()?;
     let texture_info = TextureInfo {
         width: texture_result.width,
         height: texture_result.height,
@@ -53,7 +61,7 @@ pub(crate) fn texture_result_from_extension_and_bytes(
     extension: &str,
     bytes: &[u8],
     settings: TextureSettings,
-) -> Option<TextureResult> {
+) -> Result<TextureResult, AssetErrorKind> {
     match &*extension {
         #[cfg(feature = "png")]
         "png" => {
@@ -61,7 +69,8 @@ pub(crate) fn texture_result_from_extension_and_bytes(
                 width,
                 height,
                 pixels,
-            } = imagine::png::png_try_bitmap_rgba(&bytes, true).unwrap();
+            } = imagine::png::png_try_bitmap_rgba(&bytes, true)
+                .map_err(|e| AssetErrorKind::Decode(format!("{:?}", e)))?;
 
             // TODO: Need to convert to appropriate color space here (if necessary)
 
@@ -75,7 +84,7 @@ pub(crate) fn texture_result_from_extension_and_bytes(
             }
             */
 
-            Some(TextureResult {
+            Ok(TextureResult {
                 data: TextureData::Bytes(Box::new(pixels)),
                 pixel_format: koi_graphics_context::PixelFormat::RGBA8Unorm,
                 width: width as _,
@@ -100,8 +109,12 @@ pub(crate) fn texture_result_from_extension_and_bytes(
             let reader = std::io::BufReader::new(&*bytes);
 
             let mut decoder = jpeg_decoder::Decoder::new(reader);
-            let mut pixels = decoder.decode().expect("failed to decode image");
-            let metadata = decoder.info().unwrap();
+            let mut pixels = decoder
+                .decode()
+                .map_err(|e| AssetErrorKind::Decode(e.to_string()))?;
+            let metadata = decoder
+                .info()
+                .ok_or_else(|| AssetErrorKind::Decode("missing JPEG metadata".into()))?;
 
             let pixel_format = match metadata.pixel_format {
                 jpeg_decoder::PixelFormat::RGB24 => {
@@ -125,20 +138,19 @@ pub(crate) fn texture_result_from_extension_and_bytes(
                     }
                 }
                 jpeg_decoder::PixelFormat::CMYK32 => {
-                    panic!("CMYK is currently unsupported")
+                    return Err(AssetErrorKind::UnsupportedFormat("CMYK JPEG".into()));
                 } // _ => unimplemented!("Unsupported Jpeg pixel format: {:?}", metadata.pixel_format,),
             };
-            Some(TextureResult {
+            Ok(TextureResult {
                 data: TextureData::Bytes(Box::new(pixels)),
                 pixel_format,
                 width: metadata.width as u32,
                 height: metadata.height as u32,
             })
         }
-        _ => {
-            println!("Error loading image. Unsupported image file extension: {extension} ");
-            None
-        }
+        _ => Err(AssetErrorKind::UnsupportedFormat(format!(
+            "image file extension \"{extension}\""
+        ))),
     }
 }
 
@@ -157,18 +169,18 @@ pub fn initialize_textures(renderer: &mut crate::Renderer) -> koi_assets::AssetS
     async fn load(
         path: String,
         #[allow(unused)] settings: koi_graphics_context::TextureSettings,
-    ) -> Option<TextureResult> {
+    ) -> Result<TextureResult, AssetErrorKind> {
         #[allow(unused)]
         let extension = std::path::Path::new(&path)
             .extension()
             .and_then(std::ffi::OsStr::to_str)
-            .expect("Expected image file extension")
+            .unwrap_or_default()
             .to_lowercase();
         #[cfg(not(target_arch = "wasm32"))]
         {
             let bytes = koi_fetch::fetch_bytes(&path)
                 .await
-                .unwrap_or_else(|_| panic!("Failed to open file: {}", path));
+                .map_err(AssetErrorKind::Fetch)?;
             texture_result_from_extension_and_bytes(&extension, &bytes, settings)
         }
 
@@ -177,14 +189,27 @@ pub fn initialize_textures(renderer: &mut crate::Renderer) -> koi_assets::AssetS
         // premultiplied textures.
         #[cfg(target_arch = "wasm32")]
         {
+            // Embedded and other in-memory files can't be loaded by the browser.
+            if koi_fetch::is_in_memory(&path) {
+                let bytes = koi_fetch::fetch_bytes(&path)
+                    .await
+                    .map_err(AssetErrorKind::Fetch)?;
+                return texture_result_from_extension_and_bytes(&extension, &bytes, settings);
+            }
+
+            let url = koi_fetch::resolve_file_path_unchecked(&path).unwrap_or(path);
             let kwasm::libraries::ImageLoadResult {
                 image_js_object,
                 width,
                 height,
-            } = kwasm::libraries::load_image(&path)
+            } = kwasm::libraries::load_image(&url)
                 .await
-                .unwrap_or_else(|_| panic!("Failed to open file: {}", path));
-            Some(TextureResult {
+                .map_err(|_| {
+                    AssetErrorKind::Fetch(koi_fetch::FetchError::Network(
+                        "could not load image".into(),
+                    ))
+                })?;
+            Ok(TextureResult {
                 data: TextureData::JSObject(image_js_object.to_dynamic()),
                 width,
                 height,
@@ -197,8 +222,8 @@ pub fn initialize_textures(renderer: &mut crate::Renderer) -> koi_assets::AssetS
         source: TextureResult,
         settings: koi_graphics_context::TextureSettings,
         resources: &Resources,
-    ) -> Option<Texture> {
-        Some(new_texture_from_texture_load_data(
+    ) -> Result<Texture, AssetErrorKind> {
+        Ok(new_texture_from_texture_load_data(
             &mut resources.get::<crate::Renderer>().raw_graphics_context,
             source,
             settings,
@@ -235,6 +260,21 @@ pub fn initialize_textures(renderer: &mut crate::Renderer) -> koi_assets::AssetS
         )),
         &Texture::DEFAULT_NORMAL,
     );
+
+    textures.add_and_leak(
+        Texture(renderer.raw_graphics_context.new_texture_with_data(
+            1,
+            1,
+            1,
+            &[[255, 0, 255, 255]],
+            koi_graphics_context::TextureSettings {
+                srgb: false,
+                ..Default::default()
+            },
+        )),
+        &Texture::ERROR,
+    );
+    textures.set_error_placeholder(Texture::ERROR);
     textures
 }
 
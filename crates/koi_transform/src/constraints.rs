@@ -0,0 +1,418 @@
+//! Components that drive an entity's [Transform] from another entity.
+//!
+//! Constraints are evaluated by [update_constraints] in `PostFixedUpdate`, after gameplay code
+//! and [crate::FollowPath]s, and before global transforms are updated. Kinds are applied in a fixed order:
+//! [Follow], then [CopyTransform], then [LookAt].
+//! Within each kind parents are constrained before their children.
+//! Inactive entities aren't constrained.
+//!
+//! [Billboard]s are evaluated by [update_billboards] whenever global transforms are updated,
+//! including right before rendering, so they face cameras moved after the last fixed update.
+//!
+//! Constraints work in world space so they behave correctly for entities inside hierarchies.
+
+use crate::{Transform, WorldTransformExtension};
+use kmath::*;
+use koi_ecs::{Entity, EntityMigrator, HierachyExtension, WorldClonableTrait};
+
+/// Which axes of a [Vec3] to affect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Axes {
+    pub x: bool,
+    pub y: bool,
+    pub z: bool,
+}
+
+impl Axes {
+    pub const ALL: Self = Self {
+        x: true,
+        y: true,
+        z: true,
+    };
+    pub const NONE: Self = Self {
+        x: false,
+        y: false,
+        z: false,
+    };
+
+    fn select(&self, from: Vec3, to: Vec3) -> Vec3 {
+        Vec3::new(
+            if self.x { to.x } else { from.x },
+            if self.y { to.y } else { from.y },
+            if self.z { to.z } else { from.z },
+        )
+    }
+}
+
+/// Rotates this entity so its forward direction points at `target`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LookAt {
+    pub target: Entity,
+    pub up: Vec3,
+    /// From 0.0 (no effect) to 1.0 (fully looking at the target).
+    pub weight: f32,
+}
+
+impl LookAt {
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            up: Vec3::Y,
+            weight: 1.0,
+        }
+    }
+}
+
+impl WorldClonableTrait for LookAt {
+    fn clone_with_context(&self, entity_migrator: &EntityMigrator) -> Self {
+        Self {
+            target: migrate_or_dangling(entity_migrator, self.target),
+            ..*self
+        }
+    }
+}
+
+/// Copies the world position, rotation, or scale of `source`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CopyTransform {
+    pub source: Entity,
+    pub position: Axes,
+    /// Added to the copied position, in `source`'s space.
+    pub position_offset: Vec3,
+    pub rotation: bool,
+    /// Applied after the copied rotation.
+    pub rotation_offset: Quat,
+    pub scale: Axes,
+    /// From 0.0 (no effect) to 1.0 (fully copied).
+    pub weight: f32,
+}
+
+impl CopyTransform {
+    /// Copies everything with no offset.
+    pub fn new(source: Entity) -> Self {
+        Self {
+            source,
+            position: Axes::ALL,
+            position_offset: Vec3::ZERO,
+            rotation: true,
+            rotation_offset: Quat::IDENTITY,
+            scale: Axes::ALL,
+            weight: 1.0,
+        }
+    }
+}
+
+impl WorldClonableTrait for CopyTransform {
+    fn clone_with_context(&self, entity_migrator: &EntityMigrator) -> Self {
+        Self {
+            source: migrate_or_dangling(entity_migrator, self.source),
+            ..*self
+        }
+    }
+}
+
+/// Smoothly moves this entity towards `target`'s world position plus `offset`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Follow {
+    pub target: Entity,
+    /// Added to the target's world position.
+    pub offset: Vec3,
+    /// Seconds to close half of the remaining distance. 0.0 follows exactly.
+    pub damping: f32,
+}
+
+impl Follow {
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            offset: Vec3::ZERO,
+            damping: 0.0,
+        }
+    }
+}
+
+impl WorldClonableTrait for Follow {
+    fn clone_with_context(&self, entity_migrator: &EntityMigrator) -> Self {
+        Self {
+            target: migrate_or_dangling(entity_migrator, self.target),
+            ..*self
+        }
+    }
+}
+
+/// Rotates this entity to face `camera`, for sprites and labels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Billboard {
+    pub camera: Entity,
+    /// Only rotate around the world Y axis so the entity stays upright.
+    pub lock_vertical: bool,
+}
+
+impl Billboard {
+    pub fn new(camera: Entity) -> Self {
+        Self {
+            camera,
+            lock_vertical: false,
+        }
+    }
+}
+
+impl WorldClonableTrait for Billboard {
+    fn clone_with_context(&self, entity_migrator: &EntityMigrator) -> Self {
+        Self {
+            camera: migrate_or_dangling(entity_migrator, self.camera),
+            ..*self
+        }
+    }
+}
+
+/// Entities that weren't cloned are replaced with [Entity::DANGLING] so the constraint is skipped.
+fn migrate_or_dangling(entity_migrator: &EntityMigrator, entity: Entity) -> Entity {
+    entity_migrator.migrate(entity).unwrap_or(Entity::DANGLING)
+}
+
+/// Active entities with a constraint `C`, sorted so parents come before their children.
+fn constrained_entities<C: Copy + Send + Sync + 'static>(
+    world: &koi_ecs::World,
+) -> Vec<(Entity, C)> {
+    let mut entities: Vec<(usize, Entity, C)> = world
+        .query::<koi_ecs::Without<(&C, &Transform), &koi_ecs::InactiveInHierarchy>>()
+        .iter()
+        .map(|(entity, (constraint, _))| {
+            (world.iterate_ancestors(entity).count(), entity, *constraint)
+        })
+        .collect();
+    entities.sort_by_key(|(depth, _, _)| *depth);
+    entities
+        .into_iter()
+        .map(|(_, entity, constraint)| (entity, constraint))
+        .collect()
+}
+
+fn world_position(world: &koi_ecs::World, entity: Entity) -> Option<Vec3> {
+    Some(world.world_matrix(entity).ok()?.extract_translation())
+}
+
+fn world_rotation(world: &koi_ecs::World, entity: Entity) -> Option<Quat> {
+    Some(world.world_matrix(entity).ok()?.extract_rotation())
+}
+
+fn look_rotation(position: Vec3, target: Vec3, up: Vec3) -> Quat {
+    Transform::new_looking_at(position, target, up).rotation
+}
+
+/// Applies all constraints except [Billboard]s. This runs automatically in `PostFixedUpdate`.
+pub fn update_constraints(world: &mut koi_ecs::World, time_step_seconds: f32) {
+    for (entity, follow) in constrained_entities::<Follow>(world) {
+        let (Some(position), Some(target)) = (
+            world_position(world, entity),
+            world_position(world, follow.target),
+        ) else {
+            continue;
+        };
+        let target = target + follow.offset;
+        let amount = if follow.damping > 0.0 {
+            1.0 - 0.5f32.powf(time_step_seconds / follow.damping)
+        } else {
+            1.0
+        };
+        let _ = world.set_world_position(entity, position.lerp(target, amount));
+    }
+
+    for (entity, copy) in constrained_entities::<CopyTransform>(world) {
+        let (Ok(source_matrix), Ok(matrix)) =
+            (world.world_matrix(copy.source), world.world_matrix(entity))
+        else {
+            continue;
+        };
+        let (source_position, source_rotation, source_scale) =
+            source_matrix.to_translation_rotation_scale();
+        let (position, rotation, scale) = matrix.to_translation_rotation_scale();
+
+        let target_position = copy.position.select(
+            position,
+            source_position + source_rotation.rotate_vector3(copy.position_offset),
+        );
+        let _ = world.set_world_position(entity, position.lerp(target_position, copy.weight));
+
+        if copy.rotation {
+            let target_rotation = source_rotation * copy.rotation_offset;
+            let _ = world.set_world_rotation(entity, rotation.slerp(target_rotation, copy.weight));
+        }
+
+        // Scale is copied as a ratio of world scales so it works under scaled parents.
+        let target_scale = scale.lerp(copy.scale.select(scale, source_scale), copy.weight);
+        let ratio = |target: f32, current: f32| {
+            if current == 0.0 {
+                1.0
+            } else {
+                target / current
+            }
+        };
+        if let Ok(mut transform) = world.get::<&mut Transform>(entity) {
+            transform.scale = transform.scale.mul_by_component(Vec3::new(
+                ratio(target_scale.x, scale.x),
+                ratio(target_scale.y, scale.y),
+                ratio(target_scale.z, scale.z),
+            ));
+        }
+    }
+
+    for (entity, look_at) in constrained_entities::<LookAt>(world) {
+        let (Some(position), Some(rotation), Some(target)) = (
+            world_position(world, entity),
+            world_rotation(world, entity),
+            world_position(world, look_at.target),
+        ) else {
+            continue;
+        };
+        let target_rotation = look_rotation(position, target, look_at.up);
+        let _ = world.set_world_rotation(entity, rotation.slerp(target_rotation, look_at.weight));
+    }
+}
+
+/// Rotates every [Billboard] to face its camera.
+/// This runs automatically whenever global transforms are updated.
+pub fn update_billboards(world: &mut koi_ecs::World) {
+    for (entity, billboard) in constrained_entities::<Billboard>(world) {
+        let (Some(position), Ok(camera_matrix)) = (
+            world_position(world, entity),
+            world.world_matrix(billboard.camera),
+        ) else {
+            continue;
+        };
+        let rotation = if billboard.lock_vertical {
+            // Face away from the camera so the entity's front is visible.
+            let mut direction = position - camera_matrix.extract_translation();
+            direction.y = 0.0;
+            if direction.length_squared() == 0.0 {
+                continue;
+            }
+            look_rotation(position, position + direction, Vec3::Y)
+        } else {
+            camera_matrix.extract_rotation()
+        };
+        let _ = world.set_world_rotation(entity, rotation);
+    }
+}
+
+#[test]
+fn follow_inside_hierarchy_test() {
+    let mut world = koi_ecs::World::new();
+    let target = world.spawn((Transform::new().with_position(Vec3::new(4.0, 0.0, 0.0)),));
+    let parent = world.spawn((Transform::new()
+        .with_position(Vec3::Y)
+        .with_scale(Vec3::fill(2.0)),));
+    let follower = world.spawn((Transform::new(), Follow::new(target)));
+    world.set_parent(parent, follower).unwrap();
+
+    update_constraints(&mut world, 0.1);
+    assert_eq!(
+        world_position(&world, follower),
+        Some(Vec3::new(4.0, 0.0, 0.0))
+    );
+}
+
+#[test]
+fn look_at_test() {
+    let mut world = koi_ecs::World::new();
+    let target = world.spawn((Transform::new().with_position(Vec3::new(3.0, 0.0, 0.0)),));
+    let looker = world.spawn((Transform::new(), LookAt::new(target)));
+    let inactive = world.spawn((
+        Transform::new(),
+        LookAt::new(target),
+        koi_ecs::InactiveInHierarchy,
+    ));
+
+    update_constraints(&mut world, 0.1);
+    let forward = world.get::<&Transform>(looker).unwrap().forward();
+    assert!((forward - Vec3::X).length() < 0.01);
+    assert_eq!(
+        *world.get::<&Transform>(inactive).unwrap(),
+        Transform::new()
+    );
+}
+
+#[test]
+fn copy_transform_test() {
+    let mut world = koi_ecs::World::new();
+    let source = world.spawn((Transform::new()
+        .with_position(Vec3::new(1.0, 2.0, 3.0))
+        .with_scale(Vec3::fill(4.0)),));
+
+    // Only the selected axes are copied.
+    let partial = world.spawn((
+        Transform::new().with_position(Vec3::new(0.0, 5.0, 0.0)),
+        CopyTransform {
+            position: Axes {
+                x: true,
+                y: false,
+                z: true,
+            },
+            scale: Axes::NONE,
+            ..CopyTransform::new(source)
+        },
+    ));
+
+    // World scale is copied even under a scaled parent.
+    let parent = world.spawn((Transform::new()
+        .with_position(Vec3::Y)
+        .with_scale(Vec3::fill(2.0)),));
+    let full = world.spawn((Transform::new(), CopyTransform::new(source)));
+    world.set_parent(parent, full).unwrap();
+
+    update_constraints(&mut world, 0.1);
+    let partial = *world.get::<&Transform>(partial).unwrap();
+    assert_eq!(partial.position, Vec3::new(1.0, 5.0, 3.0));
+    assert_eq!(partial.scale, Vec3::ONE);
+
+    let (position, _, scale) = world
+        .world_matrix(full)
+        .unwrap()
+        .to_translation_rotation_scale();
+    assert!((position - Vec3::new(1.0, 2.0, 3.0)).length() < 0.01);
+    assert!((scale - Vec3::fill(4.0)).length() < 0.01);
+}
+
+#[test]
+fn billboard_test() {
+    let mut world = koi_ecs::World::new();
+    let camera = world.spawn((Transform::new_looking_at(
+        Vec3::new(0.0, 3.0, 5.0),
+        Vec3::ZERO,
+        Vec3::Y,
+    ),));
+    let billboard = world.spawn((Transform::new(), Billboard::new(camera)));
+    let upright = world.spawn((
+        Transform::new(),
+        Billboard {
+            lock_vertical: true,
+            ..Billboard::new(camera)
+        },
+    ));
+
+    update_billboards(&mut world);
+    let camera_forward = world.get::<&Transform>(camera).unwrap().forward();
+    let forward = world.get::<&Transform>(billboard).unwrap().forward();
+    assert!((forward - camera_forward).length() < 0.01);
+    let forward = world.get::<&Transform>(upright).unwrap().forward();
+    assert!((forward - -Vec3::Z).length() < 0.01);
+}
+
+#[test]
+fn clone_constraints_test() {
+    let mut world_cloner = koi_ecs::WorldCloner::new();
+    world_cloner.register_clone_type::<LookAt>();
+
+    let mut world_a = koi_ecs::World::new();
+    let target = world_a.spawn((Transform::new(),));
+    let looker = world_a.spawn((LookAt::new(target),));
+    world_a.despawn(target).unwrap();
+
+    let mut world_b = koi_ecs::World::new();
+    let target_b = world_b.spawn(());
+    let migrator = world_cloner.clone_world(&mut world_a, &mut world_b);
+    let looker = migrator.migrate(looker).unwrap();
+    // The target wasn't cloned so it mustn't alias another entity.
+    assert_ne!(world_b.get::<&LookAt>(looker).unwrap().target, target_b);
+}
@@ -0,0 +1,117 @@
+use crate::Transform;
+use kmath::*;
+use koi_animation::{ArcLengthTable, Spline, SplineTrait};
+
+/// Moves an entity's [Transform] along a spline at a constant speed.
+///
+/// The spline is in the space of the entity's parent.
+/// Updated by [update_follow_paths] in `PostFixedUpdate` before constraints.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FollowPath {
+    spline: Spline,
+    arc_lengths: ArcLengthTable,
+    /// Units per second. Negative values move backwards.
+    pub speed: f32,
+    /// How far along the spline the entity is.
+    pub distance: f32,
+    /// Wrap around to the start instead of stopping at the end.
+    pub looped: bool,
+    /// Rotate so the entity's forward direction points along the spline.
+    pub orient_to_tangent: bool,
+    pub up: Vec3,
+}
+
+impl FollowPath {
+    pub fn new(spline: impl Into<Spline>, speed: f32) -> Self {
+        let spline = spline.into();
+        Self {
+            arc_lengths: ArcLengthTable::new(&spline, 16),
+            spline,
+            speed,
+            distance: 0.0,
+            looped: false,
+            orient_to_tangent: true,
+            up: Vec3::Y,
+        }
+    }
+
+    pub fn spline(&self) -> &Spline {
+        &self.spline
+    }
+
+    pub fn set_spline(&mut self, spline: impl Into<Spline>) {
+        self.spline = spline.into();
+        self.arc_lengths = ArcLengthTable::new(&self.spline, 16);
+    }
+
+    pub fn length(&self) -> f32 {
+        self.arc_lengths.length()
+    }
+
+    /// True when a path that isn't looped has reached its end.
+    pub fn is_finished(&self) -> bool {
+        !self.looped
+            && ((self.speed >= 0.0 && self.distance >= self.length())
+                || (self.speed < 0.0 && self.distance <= 0.0))
+    }
+
+    /// Starts at the point on the spline closest to `position`, like the entity's current position.
+    pub fn start_nearest_to(&mut self, position: Vec3) {
+        let t = self.spline.closest_t(position);
+        self.distance = self.arc_lengths.distance_at_t(t);
+    }
+}
+
+impl koi_ecs::WorldClonableTrait for FollowPath {
+    fn clone_with_context(&self, _entity_migrator: &koi_ecs::EntityMigrator) -> Self {
+        self.clone()
+    }
+}
+
+/// Advances every [FollowPath]. This runs automatically in `PostFixedUpdate`.
+pub fn update_follow_paths(world: &mut koi_ecs::World, time_step_seconds: f32) {
+    let query = world.query_mut::<koi_ecs::Without<
+        (&mut FollowPath, &mut Transform),
+        &koi_ecs::InactiveInHierarchy,
+    >>();
+    for (_, (follow_path, transform)) in query {
+        let length = follow_path.length();
+        let mut distance = follow_path.distance + follow_path.speed * time_step_seconds;
+        if follow_path.looped && length > 0.0 {
+            distance = distance.rem_euclid(length);
+        } else {
+            distance = distance.clamp(0.0, length);
+        }
+        follow_path.distance = distance;
+
+        let t = follow_path.arc_lengths.t_at_distance(distance);
+        transform.position = follow_path.spline.position(t);
+        if follow_path.orient_to_tangent {
+            let mut tangent = follow_path.spline.tangent(t);
+            if follow_path.speed < 0.0 {
+                tangent = -tangent;
+            }
+            if tangent != Vec3::ZERO {
+                transform.rotation = Transform::new_looking_at(
+                    transform.position,
+                    transform.position + tangent,
+                    follow_path.up,
+                )
+                .rotation;
+            }
+        }
+    }
+}
+
+#[test]
+fn follow_path_constant_speed_test() {
+    let spline =
+        koi_animation::CatmullRomSpline::new(vec![Vec3::ZERO, Vec3::X, Vec3::X * 3.0], false);
+    let mut world = koi_ecs::World::new();
+    let entity = world.spawn((Transform::new(), FollowPath::new(spline, 1.0)));
+
+    update_follow_paths(&mut world, 2.0);
+    let transform = *world.get::<&Transform>(entity).unwrap();
+    assert!((transform.position - Vec3::X * 2.0).length() < 0.01);
+    assert!((transform.forward() - Vec3::X).length() < 0.01);
+}
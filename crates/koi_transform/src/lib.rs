@@ -4,23 +4,65 @@ use koi_ecs::Component;
 
 pub mod transform_plugin;
 
-#[derive(Clone, Copy, Debug, Component)]
-pub struct GlobalTransform(Transform);
+mod world_space;
+pub use world_space::*;
+
+mod constraints;
+pub use constraints::*;
+
+mod follow_path;
+pub use follow_path::*;
+
+/// An entity's transform relative to the world, computed from its [Transform]
+/// and the [Transform]s of its ancestors.
+///
+/// The full matrix is stored so shear from non-uniformly scaled parents isn't lost.
+/// Derefs to the matrix decomposed into a position, rotation, and scale.
+#[derive(Clone, Copy, Debug, PartialEq, Component)]
+pub struct GlobalTransform {
+    matrix: Mat4,
+    decomposed: Transform,
+}
 
 impl core::ops::Deref for GlobalTransform {
     type Target = Transform;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.decomposed
     }
 }
 
 impl GlobalTransform {
+    pub fn from_mat4(matrix: Mat4) -> Self {
+        Self {
+            matrix,
+            decomposed: Transform::from_mat4(matrix),
+        }
+    }
+
+    /// The decomposed position, rotation, and scale.
     pub fn inner(&self) -> Transform {
-        self.0.clone()
+        self.decomposed
+    }
+
+    /// The full matrix that transforms points from local space to world space.
+    pub fn local_to_world(&self) -> Mat4 {
+        self.matrix
+    }
+
+    pub fn world_to_local(&self) -> Mat4 {
+        self.matrix.inversed()
+    }
+
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.matrix.transform_point(point)
+    }
+
+    pub fn transform_vector(&self, vector: Vec3) -> Vec3 {
+        self.matrix.transform_vector(vector)
     }
 }
 
-#[derive(Clone, Copy, Debug, Component)]
+#[derive(Clone, Copy, Debug, PartialEq, Component)]
 pub struct Transform {
     /// Position relative to parent
     pub position: Vec3,
@@ -85,7 +127,8 @@ impl Transform {
         Mat4::from_translation_rotation_scale(self.position, self.rotation, self.scale)
     }
 
-    /// This doesn't correctly respect global vs local transforms.
+    /// Rotates to look at `target` in the parent's space.
+    /// Use [WorldTransformExtension::look_at] to look at a point in world space.
     #[must_use]
     pub fn looking_at(mut self, target: Vec3, up: Vec3) -> Self {
         let rotation = Mat4::looking_at(self.position, target, up)
@@ -1,47 +1,348 @@
 use koi_ecs::*;
+use std::collections::HashSet;
+
+/// Below this many changed subtrees propagation runs on the current thread.
+#[cfg(not(target_arch = "wasm32"))]
+const PARALLEL_ROOT_THRESHOLD: usize = 512;
+
+/// What a [crate::GlobalTransform] was last computed from.
+#[derive(Clone, Copy)]
+struct PropagationCache {
+    /// `None` for entities without a [crate::Transform] that only pass their parent's through.
+    local: Option<crate::Transform>,
+    parent: Option<Entity>,
+}
+
+struct GlobalTransformUpdate {
+    entity: Entity,
+    cache: PropagationCache,
+    /// `None` if the entity has no [crate::Transform] and so no [crate::GlobalTransform].
+    global_matrix: Option<kmath::Mat4>,
+}
+
+/// What every thread reads while collecting updates.
+struct PropagationContext<'a> {
+    world: &'a koi_ecs::World,
+    roots: &'a [Entity],
+    /// Entities that changed. They and everything below them are recomputed.
+    changed: &'a HashSet<Entity>,
+    /// Ancestors of changed entities. Subtrees outside of these are skipped.
+    changed_below: &'a HashSet<Entity>,
+}
 
 pub struct TransformHelper {
     command_buffer: koi_ecs::CommandBuffer,
+    roots: Vec<Entity>,
+    changed: HashSet<Entity>,
+    changed_below: HashSet<Entity>,
+    updates: Vec<GlobalTransformUpdate>,
+    #[cfg(not(target_arch = "wasm32"))]
+    threads: Option<PropagationThreads>,
+}
+
+impl TransformHelper {
+    fn new() -> Self {
+        Self {
+            command_buffer: koi_ecs::CommandBuffer::new(),
+            roots: Vec::new(),
+            changed: HashSet::new(),
+            changed_below: HashSet::new(),
+            updates: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            threads: None,
+        }
+    }
+}
+
+/// Threads kept alive between propagations so large scenes don't spawn threads every frame.
+#[cfg(not(target_arch = "wasm32"))]
+struct PropagationThreads {
+    jobs: Vec<std::sync::mpsc::Sender<PropagationJob>>,
+    results: std::sync::mpsc::Receiver<std::thread::Result<Vec<GlobalTransformUpdate>>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct PropagationJob {
+    context: *const PropagationContext<'static>,
+    roots: std::ops::Range<usize>,
 }
 
-fn add_global_transform_recursive(
-    world: &koi_ecs::World,
-    commands: &mut koi_ecs::CommandBuffer,
+// Safety: The context is only read, and [PropagationThreads::run] waits for
+// every job to finish before the context it points to goes away.
+#[cfg(not(target_arch = "wasm32"))]
+unsafe impl Send for PropagationJob {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PropagationJob {
+    fn run(&self) -> Vec<GlobalTransformUpdate> {
+        let context = unsafe { &*self.context };
+        let mut updates = Vec::new();
+        collect_updates(context, &context.roots[self.roots.clone()], &mut updates);
+        updates
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl PropagationThreads {
+    fn new() -> Self {
+        let thread_count = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let (result_sender, results) = std::sync::mpsc::channel();
+        let jobs = (0..thread_count)
+            .map(|i| {
+                let (job_sender, job_receiver) = std::sync::mpsc::channel::<PropagationJob>();
+                let result_sender = result_sender.clone();
+                std::thread::Builder::new()
+                    .name(format!("koi_transform {i}"))
+                    .spawn(move || {
+                        for job in job_receiver {
+                            // Panics are sent back so the job is always reported as finished.
+                            let result =
+                                std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                                    job.run()
+                                }));
+                            if result_sender.send(result).is_err() {
+                                break;
+                            }
+                        }
+                    })
+                    .unwrap();
+                job_sender
+            })
+            .collect();
+        Self { jobs, results }
+    }
+
+    /// Collects updates for all of `context.roots`, split across the threads.
+    fn run(&self, context: &PropagationContext, updates: &mut Vec<GlobalTransformUpdate>) {
+        let root_count = context.roots.len();
+        let chunk_size = (root_count + self.jobs.len() - 1) / self.jobs.len();
+        let context_pointer =
+            context as *const PropagationContext as *const PropagationContext<'static>;
+
+        let mut sent = 0;
+        for (i, jobs) in self.jobs.iter().enumerate() {
+            let start = (i * chunk_size).min(root_count);
+            let end = (start + chunk_size).min(root_count);
+            let job = PropagationJob {
+                context: context_pointer,
+                roots: start..end,
+            };
+            match jobs.send(job) {
+                Ok(()) => sent += 1,
+                Err(error) => collect_updates(context, &context.roots[error.0.roots], updates),
+            }
+        }
+
+        // Wait for every job before returning, even if one panicked.
+        let mut panic = None;
+        for _ in 0..sent {
+            match self.results.recv() {
+                Ok(Ok(thread_updates)) => updates.extend(thread_updates),
+                Ok(Err(payload)) => panic = Some(payload),
+                // Every thread has stopped so none can still be reading the context.
+                Err(_) => break,
+            }
+        }
+        if let Some(payload) = panic {
+            std::panic::resume_unwind(payload);
+        }
+    }
+}
+
+/// Finds entities whose [crate::Transform] or parent changed, or that weren't propagated before,
+/// and the topmost ancestors of each to start propagating from.
+fn find_changes(world: &koi_ecs::World, transform_helper: &mut TransformHelper) {
+    let TransformHelper {
+        roots,
+        changed,
+        changed_below,
+        ..
+    } = transform_helper;
+
+    for (entity, (cache, local, child)) in world
+        .query::<(&PropagationCache, Option<&crate::Transform>, Option<&Child>)>()
+        .iter()
+    {
+        if cache.local != local.copied() || cache.parent != child.map(|child| child.parent()) {
+            changed.insert(entity);
+        }
+    }
+    // Parents and children without a Transform are propagated too, to pass their parent's through.
+    changed.extend(
+        world
+            .query::<koi_ecs::Without<&crate::Transform, &PropagationCache>>()
+            .iter()
+            .map(|(entity, _)| entity),
+    );
+    changed.extend(
+        world
+            .query::<koi_ecs::Without<&Parent, &PropagationCache>>()
+            .iter()
+            .map(|(entity, _)| entity),
+    );
+    changed.extend(
+        world
+            .query::<koi_ecs::Without<&Child, &PropagationCache>>()
+            .iter()
+            .map(|(entity, _)| entity),
+    );
+
+    for entity in changed.iter() {
+        let mut top = Some(*entity);
+        for ancestor in world.iterate_ancestors(*entity) {
+            // The rest of the ancestors were already visited from another changed entity.
+            if !changed_below.insert(ancestor) {
+                top = None;
+                break;
+            }
+            top = Some(ancestor);
+        }
+        roots.extend(top);
+    }
+    roots.sort_unstable();
+    roots.dedup();
+}
+
+fn collect_updates_recursive(
+    context: &PropagationContext,
+    entity: Entity,
+    parent: Option<Entity>,
     parent_matrix: kmath::Mat4,
-    entity: koi_ecs::Entity,
-) -> Option<()> {
-    // TODO: Right now this does not handle cases where intermediate children
-    // do not have a Transform.
-    // Should this continue to percolate the parent transform even in those cases?
+    parent_changed: bool,
+    updates: &mut Vec<GlobalTransformUpdate>,
+) {
+    let mut changed = parent_changed || context.changed.contains(&entity);
+    if !changed && !context.changed_below.contains(&entity) {
+        return;
+    }
+
+    // Entities without a Transform, like groups, pass their parent's matrix to their children.
+    let local = context
+        .world
+        .get::<&crate::Transform>(entity)
+        .ok()
+        .map(|transform| *transform);
+
+    let cached_matrix = match local {
+        Some(_) => context
+            .world
+            .get::<&crate::GlobalTransform>(entity)
+            .ok()
+            .map(|global_transform| global_transform.local_to_world()),
+        None => Some(parent_matrix),
+    };
+    let global_matrix = match cached_matrix {
+        Some(global_matrix) if !changed => global_matrix,
+        _ => {
+            changed = true;
+            let global_matrix = match local {
+                Some(local) => parent_matrix * local.local_to_world(),
+                None => parent_matrix,
+            };
+            updates.push(GlobalTransformUpdate {
+                entity,
+                cache: PropagationCache { local, parent },
+                global_matrix: local.map(|_| global_matrix),
+            });
+            global_matrix
+        }
+    };
 
-    let transform = world.get::<&crate::Transform>(entity).ok()?;
-    let new_matrix = parent_matrix * transform.local_to_world();
-    let new_transform = crate::Transform::from_mat4(new_matrix);
-    commands.insert_one(entity, crate::GlobalTransform(new_transform));
-    for child in world.iterate_children(entity) {
-        add_global_transform_recursive(world, commands, new_matrix, child);
+    for child in context.world.iterate_children(entity) {
+        collect_updates_recursive(
+            context,
+            child,
+            Some(entity),
+            global_matrix,
+            changed,
+            updates,
+        );
     }
+}
 
-    Some(())
+fn collect_updates(
+    context: &PropagationContext,
+    roots: &[Entity],
+    updates: &mut Vec<GlobalTransformUpdate>,
+) {
+    for root in roots {
+        collect_updates_recursive(context, *root, None, kmath::Mat4::IDENTITY, false, updates);
+    }
 }
 
+/// Updates the [crate::GlobalTransform] of every entity whose [crate::Transform],
+/// parent, or ancestors changed since the last call.
+///
+/// Changes are found with one pass over every entity's cache, then only subtrees
+/// containing changes are visited so mostly static scenes are cheap.
+/// Many changed subtrees are split across threads.
+///
+/// This runs after `PostFixedUpdate` and before rendering.
+/// Call [refresh_global_transforms] to update them at another time.
 pub fn update_global_transforms(
     _event: &koi_events::Event,
     world: &mut koi_ecs::World,
     resources: &mut koi_resources::Resources,
 ) {
+    refresh_global_transforms(world, resources);
+}
+
+/// Immediately updates [crate::GlobalTransform]s, like after moving entities
+/// outside of a fixed update.
+///
+/// [crate::Billboard]s are rotated to face their cameras first.
+pub fn refresh_global_transforms(
+    world: &mut koi_ecs::World,
+    resources: &mut koi_resources::Resources,
+) {
+    crate::update_billboards(world);
+
     let transform_helper = resources.get_mut::<TransformHelper>();
     transform_helper.command_buffer.clear();
+    transform_helper.roots.clear();
+    transform_helper.changed.clear();
+    transform_helper.changed_below.clear();
+    transform_helper.updates.clear();
 
-    {
-        let mut query = world.query::<koi_ecs::Without<&crate::Transform, &Child>>();
-        for (entity, _transform) in query.iter() {
-            add_global_transform_recursive(
-                world,
-                &mut transform_helper.command_buffer,
-                kmath::Mat4::IDENTITY,
-                entity,
-            );
+    find_changes(world, transform_helper);
+    let context = PropagationContext {
+        world,
+        roots: &transform_helper.roots,
+        changed: &transform_helper.changed,
+        changed_below: &transform_helper.changed_below,
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let parallel = context.roots.len() >= PARALLEL_ROOT_THRESHOLD;
+    #[cfg(target_arch = "wasm32")]
+    let parallel = false;
+
+    if parallel {
+        #[cfg(not(target_arch = "wasm32"))]
+        transform_helper
+            .threads
+            .get_or_insert_with(PropagationThreads::new)
+            .run(&context, &mut transform_helper.updates);
+    } else {
+        collect_updates(&context, context.roots, &mut transform_helper.updates);
+    }
+
+    // Write in place where possible. Components are only inserted the first time.
+    for update in transform_helper.updates.drain(..) {
+        if let Some(global_matrix) = update.global_matrix {
+            let global_transform = crate::GlobalTransform::from_mat4(global_matrix);
+            match world.get::<&mut crate::GlobalTransform>(update.entity) {
+                Ok(mut existing) => *existing = global_transform,
+                Err(_) => transform_helper
+                    .command_buffer
+                    .insert_one(update.entity, global_transform),
+            }
+        }
+        match world.get::<&mut PropagationCache>(update.entity) {
+            Ok(mut existing) => *existing = update.cache,
+            Err(_) => transform_helper
+                .command_buffer
+                .insert_one(update.entity, update.cache),
         }
     }
     transform_helper.command_buffer.run_on(world);
@@ -49,13 +350,93 @@ pub fn update_global_transforms(
 
 pub fn initialize_plugin(resources: &mut koi_resources::Resources) {
     let world_cloner = resources.get_mut::<WorldCloner>();
-    world_cloner.register_clone_type::<crate::Transform>();
-    world_cloner.register_clone_type::<crate::GlobalTransform>();
+    world_cloner.register_diffable_type::<crate::Transform>();
+    world_cloner.register_diffable_type::<crate::GlobalTransform>();
+    world_cloner.register_diffable_type::<crate::LookAt>();
+    world_cloner.register_diffable_type::<crate::CopyTransform>();
+    world_cloner.register_diffable_type::<crate::Follow>();
+    world_cloner.register_diffable_type::<crate::Billboard>();
+    world_cloner.register_diffable_type::<crate::FollowPath>();
 
-    resources.add(TransformHelper {
-        command_buffer: koi_ecs::CommandBuffer::new(),
-    });
+    resources.add(TransformHelper::new());
     let event_handlers = resources.get_mut::<koi_events::EventHandlers>();
+    event_handlers.add_handler(koi_events::Event::PostFixedUpdate, |_, world, resources| {
+        let time_step = resources.get::<koi_time::Time>().fixed_time_step_seconds as f32;
+        crate::update_follow_paths(world, time_step);
+        crate::update_constraints(world, time_step);
+    });
     event_handlers.add_handler(koi_events::Event::PostFixedUpdate, update_global_transforms);
     koi_animation::initialize_animation_plugin::<crate::Transform>(resources);
 }
+
+#[test]
+fn incremental_global_transform_test() {
+    use kmath::*;
+
+    let mut resources = koi_resources::Resources::new();
+    resources.add(TransformHelper::new());
+    let mut world = koi_ecs::World::new();
+    let event = koi_events::Event::PostFixedUpdate;
+
+    let parent = world.spawn((crate::Transform::new().with_position(Vec3::X),));
+    let child = world.spawn((crate::Transform::new().with_position(Vec3::Y),));
+    let other_parent = world.spawn((crate::Transform::new().with_position(Vec3::Z),));
+    world.set_parent(parent, child).unwrap();
+
+    let global_position = |world: &koi_ecs::World, entity| {
+        world
+            .get::<&crate::GlobalTransform>(entity)
+            .unwrap()
+            .position
+    };
+
+    update_global_transforms(&event, &mut world, &mut resources);
+    assert_eq!(global_position(&world, child), Vec3::new(1.0, 1.0, 0.0));
+
+    // Nothing changed so no subtrees are visited.
+    update_global_transforms(&event, &mut world, &mut resources);
+    assert!(resources.get_mut::<TransformHelper>().roots.is_empty());
+
+    // Moving the parent also updates the unchanged child.
+    world.get::<&mut crate::Transform>(parent).unwrap().position = Vec3::X * 2.0;
+    update_global_transforms(&event, &mut world, &mut resources);
+    assert_eq!(global_position(&world, child), Vec3::new(2.0, 1.0, 0.0));
+
+    // Reparenting is detected even though no transforms changed.
+    world.set_parent(other_parent, child).unwrap();
+    update_global_transforms(&event, &mut world, &mut resources);
+    assert_eq!(global_position(&world, child), Vec3::new(0.0, 1.0, 1.0));
+
+    // Entities without a Transform pass their parent's through.
+    let group = world.spawn(());
+    world.set_parent(other_parent, group).unwrap();
+    world.set_parent(group, child).unwrap();
+    update_global_transforms(&event, &mut world, &mut resources);
+    assert_eq!(global_position(&world, child), Vec3::new(0.0, 1.0, 1.0));
+    world.set_parent(parent, group).unwrap();
+    update_global_transforms(&event, &mut world, &mut resources);
+    assert_eq!(global_position(&world, child), Vec3::new(2.0, 1.0, 0.0));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn parallel_global_transform_test() {
+    use kmath::*;
+
+    let mut resources = koi_resources::Resources::new();
+    resources.add(TransformHelper::new());
+    let mut world = koi_ecs::World::new();
+    let mut entities = Vec::new();
+    for i in 0..PARALLEL_ROOT_THRESHOLD * 2 {
+        let parent = world.spawn((crate::Transform::new().with_position(Vec3::X * i as f32),));
+        let child = world.spawn((crate::Transform::new().with_position(Vec3::Y),));
+        world.set_parent(parent, child).unwrap();
+        entities.push((i, child));
+    }
+
+    refresh_global_transforms(&mut world, &mut resources);
+    for (i, child) in entities {
+        let global_transform = *world.get::<&crate::GlobalTransform>(child).unwrap();
+        assert_eq!(global_transform.position, Vec3::new(i as f32, 1.0, 0.0));
+    }
+}
@@ -0,0 +1,122 @@
+use crate::Transform;
+use kmath::*;
+use koi_ecs::HierachyExtension;
+
+/// Reads and sets [Transform]s in world space, converting through the parent chain.
+///
+/// These compute from the current [Transform]s so they're correct even before
+/// [crate::GlobalTransform]s are updated.
+pub trait WorldTransformExtension {
+    /// The matrix that transforms from `entity`'s local space to world space.
+    fn world_matrix(&self, entity: koi_ecs::Entity) -> Result<Mat4, koi_ecs::NoSuchEntity>;
+
+    /// The matrix that transforms from the space of `entity`'s parent to world space.
+    /// This is the identity for entities without a parent.
+    fn parent_world_matrix(&self, entity: koi_ecs::Entity) -> Mat4;
+
+    fn set_world_position(
+        &mut self,
+        entity: koi_ecs::Entity,
+        position: Vec3,
+    ) -> Result<(), koi_ecs::NoSuchEntity>;
+
+    fn set_world_rotation(
+        &mut self,
+        entity: koi_ecs::Entity,
+        rotation: Quat,
+    ) -> Result<(), koi_ecs::NoSuchEntity>;
+
+    /// Rotates `entity` so its forward direction points at `target` in world space.
+    fn look_at(
+        &mut self,
+        entity: koi_ecs::Entity,
+        target: Vec3,
+        up: Vec3,
+    ) -> Result<(), koi_ecs::NoSuchEntity>;
+}
+
+impl WorldTransformExtension for koi_ecs::World {
+    fn world_matrix(&self, entity: koi_ecs::Entity) -> Result<Mat4, koi_ecs::NoSuchEntity> {
+        let local = self
+            .get::<&Transform>(entity)
+            .map_err(|_| koi_ecs::NoSuchEntity)?
+            .local_to_world();
+        Ok(self.parent_world_matrix(entity) * local)
+    }
+
+    fn parent_world_matrix(&self, entity: koi_ecs::Entity) -> Mat4 {
+        let mut matrix = Mat4::IDENTITY;
+        for ancestor in self.iterate_ancestors(entity) {
+            if let Ok(transform) = self.get::<&Transform>(ancestor) {
+                matrix = transform.local_to_world() * matrix;
+            }
+        }
+        matrix
+    }
+
+    fn set_world_position(
+        &mut self,
+        entity: koi_ecs::Entity,
+        position: Vec3,
+    ) -> Result<(), koi_ecs::NoSuchEntity> {
+        let local_position = self
+            .parent_world_matrix(entity)
+            .inversed()
+            .transform_point(position);
+        let mut transform = self
+            .get::<&mut Transform>(entity)
+            .map_err(|_| koi_ecs::NoSuchEntity)?;
+        transform.position = local_position;
+        Ok(())
+    }
+
+    fn set_world_rotation(
+        &mut self,
+        entity: koi_ecs::Entity,
+        rotation: Quat,
+    ) -> Result<(), koi_ecs::NoSuchEntity> {
+        let world_rotation = Mat4::from_translation_rotation_scale(Vec3::ZERO, rotation, Vec3::ONE);
+        let local_rotation =
+            (self.parent_world_matrix(entity).inversed() * world_rotation).extract_rotation();
+        let mut transform = self
+            .get::<&mut Transform>(entity)
+            .map_err(|_| koi_ecs::NoSuchEntity)?;
+        transform.rotation = local_rotation;
+        Ok(())
+    }
+
+    fn look_at(
+        &mut self,
+        entity: koi_ecs::Entity,
+        target: Vec3,
+        up: Vec3,
+    ) -> Result<(), koi_ecs::NoSuchEntity> {
+        let position = self.world_matrix(entity)?.extract_translation();
+        let rotation = Mat4::looking_at(position, target, up)
+            .inversed()
+            .extract_rotation();
+        self.set_world_rotation(entity, rotation)
+    }
+}
+
+#[test]
+fn set_world_position_test() {
+    let mut world = koi_ecs::World::new();
+    let parent = world.spawn((Transform::new()
+        .with_position(Vec3::X)
+        .with_scale(Vec3::fill(2.0)),));
+    let child = world.spawn((Transform::new(),));
+    world.set_parent(parent, child).unwrap();
+
+    world
+        .set_world_position(child, Vec3::new(3.0, 2.0, 0.0))
+        .unwrap();
+    assert_eq!(
+        world.get::<&Transform>(child).unwrap().position,
+        Vec3::new(1.0, 1.0, 0.0)
+    );
+    assert_eq!(
+        world.world_matrix(child).unwrap().extract_translation(),
+        Vec3::new(3.0, 2.0, 0.0)
+    );
+}
@@ -7,7 +7,31 @@ use koi_transform::*;
 pub use kui;
 use kui::*;
 
-pub fn initialize_plugin(world: &mut World) {
+/// Adds an [koi_input::InputRouter] if one doesn't exist yet,
+/// so this can be initialized before or after `koi_input`.
+pub fn initialize_plugin(world: &mut World, resources: &mut Resources) {
+    resources.add(UIInputHandlers(Vec::new()));
+    if resources.try_get::<koi_input::InputRouter>().is_none() {
+        resources.add(koi_input::InputRouter::new());
+    }
+    resources.get_mut::<koi_input::InputRouter>().add_consumer(
+        koi_input::InputPriority::UI,
+        |event, world, resources| {
+            let handlers: Vec<_> = resources
+                .get::<UIInputHandlers>()
+                .0
+                .iter()
+                .map(|(_, handler)| *handler)
+                .collect();
+            // Every UI sees the event, even if an earlier one handled it.
+            let mut handled = false;
+            for handler in handlers {
+                handled |= handler(world, resources, event);
+            }
+            handled
+        },
+    );
+
     let projection_matrix = projection_matrices::orthographic_gl(-1.0, 1.0, -1.0, 1.0, 0.0, 1.0);
     world.spawn((
         Transform::new(),
@@ -54,6 +78,8 @@ impl<UIState: 'static> WorldSpaceUI<UIState> {
 
         use kui::*;
 
+        route_ui_input::<UIState>(resources);
+
         let world_space_ui = Self {
             drawer: kui::Drawer::new(),
             context: StandardContext::new(style, Default::default(), fonts),
@@ -116,6 +142,8 @@ impl<UIState: 'static> ScreenSpaceUI<UIState> {
 
         use kui::*;
 
+        route_ui_input::<UIState>(resources);
+
         let screen_space_ui = Self {
             drawer: kui::Drawer::new(),
             context: StandardContext::new(style, Default::default(), fonts),
@@ -397,6 +425,33 @@ fn draw_screen_space_uis<UIState: 'static>(world: &mut World, resources: &Resour
     }
 }
 
+type UIInputHandler = fn(&mut World, &Resources, &kapp::Event) -> bool;
+
+/// The `UIState`s whose UIs receive input through the [koi_input::InputRouter].
+struct UIInputHandlers(Vec<(std::any::TypeId, UIInputHandler)>);
+
+/// Sends input to `UIState` UIs through the [koi_input::InputRouter] so events
+/// the UI handles don't reach `Input` or lower priority consumers.
+///
+/// This is called automatically when a UI is created.
+/// After calling this [update_ui_with_event] ignores input events for `UIState`.
+pub fn route_ui_input<UIState: 'static>(resources: &Resources) {
+    let Some(mut handlers) = resources.try_get::<UIInputHandlers>() else {
+        return;
+    };
+    let type_id = std::any::TypeId::of::<UIState>();
+    if !handlers.0.iter().any(|(id, _)| *id == type_id) {
+        handlers.0.push((type_id, handle_ui_event::<UIState>));
+    }
+}
+
+fn is_ui_input_routed<UIState: 'static>(resources: &Resources) -> bool {
+    let type_id = std::any::TypeId::of::<UIState>();
+    resources
+        .try_get::<UIInputHandlers>()
+        .is_some_and(|handlers| handlers.0.iter().any(|(id, _)| *id == type_id))
+}
+
 /// Returns true if the event was handled by the UI.
 pub fn update_ui_with_event<UIState: 'static>(
     world: &mut World,
@@ -404,6 +459,7 @@ pub fn update_ui_with_event<UIState: 'static>(
     event: &koi_events::Event,
 ) -> bool {
     match event {
+        koi_events::Event::KappEvent(_) if is_ui_input_routed::<UIState>(resources) => false,
         koi_events::Event::KappEvent(event) => handle_ui_event::<UIState>(world, resources, event),
         koi_events::Event::Draw => {
             draw_screen_space_uis::<UIState>(world, resources);
@@ -10,7 +10,14 @@ pub struct App {
 impl Default for App {
     fn default() -> Self {
         let mut resources = Resources::new();
-        resources.add(EventHandlers::new());
+        let mut event_handlers = EventHandlers::new();
+
+        // This runs before the standard plugins' handlers so inactive entities
+        // are already marked when transforms, audio, and rendering update.
+        event_handlers.add_handler(Event::PostFixedUpdate, |_, world, _| {
+            world.update_active_hierarchy();
+        });
+        resources.add(event_handlers);
         resources.add(Time::new());
 
         let mut s = Self {
@@ -25,8 +32,11 @@ impl Default for App {
 impl App {
     pub fn setup_world_cloner(&mut self) {
         let mut world_cloner = WorldCloner::new();
-        world_cloner.register_clone_type::<Child>();
-        world_cloner.register_clone_type::<Parent>();
+        world_cloner.register_diffable_type::<Child>();
+        world_cloner.register_diffable_type::<Parent>();
+        world_cloner.register_diffable_type::<Active>();
+        world_cloner.register_diffable_type::<Name>();
+        world_cloner.register_clone_type::<InactiveInHierarchy>();
         self.resources.add(world_cloner);
     }
 
@@ -121,6 +131,13 @@ impl App {
         ktasks::run_only_local_tasks();
         ktasks::run_tasks_unless_there_are_workers();
 
+        #[cfg(feature = "koi_input")]
+        if let Event::KappEvent(kapp_event) = &event {
+            if let Some(mut recorder) = self.resources.try_get::<InputRecorder>() {
+                recorder.record_event(kapp_event);
+            }
+        }
+
         // This funky memory-swap approach allows `EventHandlers` to be part of `Resources`
         let event_handlers = self.resources.get_mut::<EventHandlers>();
         let mut temp_event_handlers = EventHandlers::new();
@@ -140,6 +157,8 @@ impl App {
         while self.resources.get_mut::<Time>().fixed_update_ready() {
             self.handle_event(Event::FixedUpdate);
             self.handle_event(Event::PostFixedUpdate);
+            #[cfg(feature = "koi_input")]
+            self.record_fixed_update();
         }
     }
 
@@ -157,7 +176,7 @@ impl App {
         koi_audio::initialize_plugin(&mut self.resources);
 
         #[cfg(feature = "koi_ui")]
-        koi_ui::initialize_plugin(&mut self.world);
+        koi_ui::initialize_plugin(&mut self.world, &mut self.resources);
 
         koi_transform::transform_plugin::initialize_plugin(&mut self.resources);
         self
@@ -0,0 +1,494 @@
+//! Records input to a file and plays it back so that a session can be reproduced exactly.
+//!
+//! A recording holds the `Random` seed, every input event, and the boundaries between fixed
+//! updates. Playback feeds the same events to [App::handle_event] with the same fixed updates
+//! in between, so everything that reads [Input] behaves as it did while recording.
+//! Register components with a [StateHasher] resource to detect where a playback diverges.
+
+use crate::*;
+use std::hash::{Hash, Hasher};
+
+/// A 64-bit FNV-1a hasher.
+///
+/// Unlike [std::collections::hash_map::DefaultHasher] its output doesn't change between
+/// builds, so hashes saved in a recording can be compared later.
+pub struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Hashes registered components to detect when a playback diverges from its recording.
+#[derive(Default)]
+pub struct StateHasher {
+    hash_functions: Vec<Box<dyn Fn(&World, &mut StableHasher) + Send + Sync>>,
+}
+
+impl StateHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register<T: Component + Hash>(&mut self) {
+        self.register_with::<T>(|component, hasher| component.hash(hasher));
+    }
+
+    /// Registers a component that doesn't implement [Hash], like one containing floats.
+    pub fn register_with<T: Component>(&mut self, hash: fn(&T, &mut StableHasher)) {
+        self.hash_functions.push(Box::new(move |world, hasher| {
+            let mut hashes: Vec<(u64, u64)> = world
+                .query::<&T>()
+                .iter()
+                .map(|(entity, component)| {
+                    let mut component_hasher = StableHasher::default();
+                    hash(component, &mut component_hasher);
+                    (entity.to_bits().get(), component_hasher.finish())
+                })
+                .collect();
+            // Query order depends on archetypes, so sort to compare only the contents.
+            hashes.sort_unstable();
+            hashes.hash(hasher);
+        }));
+    }
+
+    pub fn hash_world(&self, world: &World) -> u64 {
+        let mut hasher = StableHasher::default();
+        for hash_function in &self.hash_functions {
+            hash_function(world, &mut hasher);
+        }
+        hasher.finish()
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Record {
+    Event(KappEvent),
+    /// A fixed update ran. Holds the [StateHasher] hash afterwards, if there was one.
+    FixedUpdate(Option<u64>),
+}
+
+/// Input recorded by [App::start_recording].
+#[derive(Clone, Debug, Default)]
+pub struct InputRecording {
+    pub seed: u64,
+    records: Vec<Record>,
+}
+
+/// Where a playback first differed from its recording.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// How many fixed updates had run, including the one that diverged.
+    pub fixed_update: usize,
+    pub expected_hash: u64,
+    pub actual_hash: u64,
+}
+
+impl std::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "playback diverged at fixed update {}: expected hash {:x}, got {:x}",
+            self.fixed_update, self.expected_hash, self.actual_hash
+        )
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// Added as a resource while recording.
+pub(crate) struct InputRecorder {
+    recording: InputRecording,
+}
+
+impl InputRecorder {
+    pub(crate) fn record_event(&mut self, event: &KappEvent) {
+        // Only events that can be written to text are recorded.
+        if event_to_text(event).is_some() {
+            self.recording.records.push(Record::Event(event.clone()));
+        }
+    }
+
+    pub(crate) fn record_fixed_update(&mut self, hash: Option<u64>) {
+        self.recording.records.push(Record::FixedUpdate(hash));
+    }
+}
+
+impl App {
+    /// Starts recording input. If there's a `Random` resource it's reseeded with `seed`.
+    pub fn start_recording(&mut self, seed: u64) {
+        #[cfg(feature = "koi_random")]
+        if let Some(mut random) = self.resources.try_get::<Random>() {
+            random.set_seed(seed);
+        }
+        self.resources.add(InputRecorder {
+            recording: InputRecording {
+                seed,
+                records: Vec::new(),
+            },
+        });
+    }
+
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.resources
+            .remove::<InputRecorder>()
+            .map(|recorder| recorder.recording)
+    }
+
+    pub(crate) fn record_fixed_update(&mut self) {
+        if let Some(mut recorder) = self.resources.try_get::<InputRecorder>() {
+            let hash = self
+                .resources
+                .try_get::<StateHasher>()
+                .map(|state_hasher| state_hasher.hash_world(&self.world));
+            recorder.record_fixed_update(hash);
+        }
+    }
+}
+
+impl InputRecording {
+    /// Replays the recording into `app`, running a fixed update wherever one ran while
+    /// recording regardless of elapsed time.
+    ///
+    /// `app` should be set up the same way as when recording, but doesn't need a window.
+    /// Stops at the first fixed update whose [StateHasher] hash differs from the recording.
+    pub fn play(&self, app: &mut App) -> Result<(), Divergence> {
+        #[cfg(feature = "koi_random")]
+        if let Some(mut random) = app.resources.try_get::<Random>() {
+            random.set_seed(self.seed);
+        }
+
+        let mut fixed_update = 0;
+        for record in &self.records {
+            match record {
+                Record::Event(event) => app.handle_event(Event::KappEvent(event.clone())),
+                Record::FixedUpdate(expected_hash) => {
+                    app.handle_event(Event::FixedUpdate);
+                    app.handle_event(Event::PostFixedUpdate);
+                    fixed_update += 1;
+
+                    let actual_hash = app
+                        .resources
+                        .try_get::<StateHasher>()
+                        .map(|state_hasher| state_hasher.hash_world(&app.world));
+                    if let (Some(expected_hash), Some(actual_hash)) = (*expected_hash, actual_hash)
+                    {
+                        if expected_hash != actual_hash {
+                            return Err(Divergence {
+                                fixed_update,
+                                expected_hash,
+                                actual_hash,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// One record per line:
+    ///
+    /// ```text
+    /// seed 1234
+    /// KeyDown Space 0.5
+    /// fixed_update 9f3a0c1e2b4d5f67
+    /// ```
+    pub fn to_text(&self) -> String {
+        let mut text = format!("seed {}\n", self.seed);
+        for record in &self.records {
+            match record {
+                Record::Event(event) => {
+                    if let Some(line) = event_to_text(event) {
+                        text += &line;
+                        text.push('\n');
+                    }
+                }
+                Record::FixedUpdate(Some(hash)) => text += &format!("fixed_update {:x}\n", hash),
+                Record::FixedUpdate(None) => text += "fixed_update\n",
+            }
+        }
+        text
+    }
+
+    /// Returns the line number of the first line that couldn't be read on failure.
+    pub fn from_text(text: &str) -> Result<Self, usize> {
+        let mut recording = InputRecording::default();
+        for (index, line) in text.lines().enumerate() {
+            let mut parts = line.split_whitespace();
+            let record = match parts.next() {
+                None => continue,
+                Some("seed") => {
+                    recording.seed = parts.next().and_then(|s| s.parse().ok()).ok_or(index + 1)?;
+                    continue;
+                }
+                Some("fixed_update") => Record::FixedUpdate(match parts.next() {
+                    Some(hash) => Some(u64::from_str_radix(hash, 16).map_err(|_| index + 1)?),
+                    None => None,
+                }),
+                Some(_) => match event_from_text(line) {
+                    Some(event) => Record::Event(event),
+                    // Skip keys this build doesn't know instead of rejecting the whole recording.
+                    None if has_unknown_key(line) => continue,
+                    None => return Err(index + 1),
+                },
+            };
+            recording.records.push(record);
+        }
+        Ok(recording)
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_to_file(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.to_text())
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_from_file(path: &str) -> std::io::Result<Self> {
+        let text = std::fs::read_to_string(path)?;
+        Self::from_text(&text).map_err(|line| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid input recording on line {}", line),
+            )
+        })
+    }
+}
+
+fn event_to_text(event: &KappEvent) -> Option<String> {
+    Some(match event {
+        KappEvent::KeyDown { key, timestamp } => {
+            format!("KeyDown {:?} {}", key, timestamp.as_secs_f64())
+        }
+        KappEvent::KeyUp { key, timestamp } => {
+            format!("KeyUp {:?} {}", key, timestamp.as_secs_f64())
+        }
+        KappEvent::KeyRepeat { key, timestamp } => {
+            format!("KeyRepeat {:?} {}", key, timestamp.as_secs_f64())
+        }
+        // Written as a code point so whitespace characters survive.
+        KappEvent::CharacterReceived { character } => {
+            format!("CharacterReceived {}", *character as u32)
+        }
+        KappEvent::PointerDown {
+            x,
+            y,
+            source,
+            button,
+            timestamp,
+            id,
+        } => format!(
+            "PointerDown {} {} {:?} {:?} {} {}",
+            x,
+            y,
+            source,
+            button,
+            id,
+            timestamp.as_secs_f64()
+        ),
+        KappEvent::PointerUp {
+            x,
+            y,
+            source,
+            button,
+            timestamp,
+            id,
+        } => format!(
+            "PointerUp {} {} {:?} {:?} {} {}",
+            x,
+            y,
+            source,
+            button,
+            id,
+            timestamp.as_secs_f64()
+        ),
+        KappEvent::PointerMoved {
+            x,
+            y,
+            source,
+            timestamp,
+            id,
+        } => format!(
+            "PointerMoved {} {} {:?} {} {}",
+            x,
+            y,
+            source,
+            id,
+            timestamp.as_secs_f64()
+        ),
+        KappEvent::MouseMotion {
+            delta_x,
+            delta_y,
+            timestamp,
+        } => format!(
+            "MouseMotion {} {} {}",
+            delta_x,
+            delta_y,
+            timestamp.as_secs_f64()
+        ),
+        // The window isn't recorded. Playback doesn't have the same windows.
+        KappEvent::Scroll {
+            delta_x,
+            delta_y,
+            timestamp,
+            ..
+        } => format!("Scroll {} {} {}", delta_x, delta_y, timestamp.as_secs_f64()),
+        _ => return None,
+    })
+}
+
+fn event_from_text(line: &str) -> Option<KappEvent> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+    if let ["CharacterReceived", character] = parts[..] {
+        return Some(KappEvent::CharacterReceived {
+            character: char::from_u32(character.parse().ok()?)?,
+        });
+    }
+    let timestamp = std::time::Duration::from_secs_f64(parts.last()?.parse().ok()?);
+    let source = |s: &str| {
+        [
+            PointerSource::Mouse,
+            PointerSource::Touch,
+            PointerSource::Pen,
+        ]
+        .into_iter()
+        .find(|source| format!("{:?}", source) == s)
+    };
+    let button = |s: &str| {
+        [
+            PointerButton::Primary,
+            PointerButton::Secondary,
+            PointerButton::Auxillary,
+        ]
+        .into_iter()
+        .find(|button| format!("{:?}", button) == s)
+    };
+
+    Some(match parts[..] {
+        ["KeyDown", key, _] => KappEvent::KeyDown {
+            key: key_from_name(key)?,
+            timestamp,
+        },
+        ["KeyUp", key, _] => KappEvent::KeyUp {
+            key: key_from_name(key)?,
+            timestamp,
+        },
+        ["KeyRepeat", key, _] => KappEvent::KeyRepeat {
+            key: key_from_name(key)?,
+            timestamp,
+        },
+        ["PointerDown", x, y, s, b, id, _] => KappEvent::PointerDown {
+            x: x.parse().ok()?,
+            y: y.parse().ok()?,
+            source: source(s)?,
+            button: button(b)?,
+            id: id.parse().ok()?,
+            timestamp,
+        },
+        ["PointerUp", x, y, s, b, id, _] => KappEvent::PointerUp {
+            x: x.parse().ok()?,
+            y: y.parse().ok()?,
+            source: source(s)?,
+            button: button(b)?,
+            id: id.parse().ok()?,
+            timestamp,
+        },
+        ["PointerMoved", x, y, s, id, _] => KappEvent::PointerMoved {
+            x: x.parse().ok()?,
+            y: y.parse().ok()?,
+            source: source(s)?,
+            id: id.parse().ok()?,
+            timestamp,
+        },
+        ["MouseMotion", delta_x, delta_y, _] => KappEvent::MouseMotion {
+            delta_x: delta_x.parse().ok()?,
+            delta_y: delta_y.parse().ok()?,
+            timestamp,
+        },
+        ["Scroll", delta_x, delta_y, _] => KappEvent::Scroll {
+            delta_x: delta_x.parse().ok()?,
+            delta_y: delta_y.parse().ok()?,
+            window_id: kapp_platform_common::WindowId::new(std::ptr::null_mut()),
+            timestamp,
+        },
+        _ => return None,
+    })
+}
+
+/// Is `line` a well formed key event for a key [key_from_name] doesn't know?
+fn has_unknown_key(line: &str) -> bool {
+    match line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["KeyDown" | "KeyUp" | "KeyRepeat", key, timestamp] => {
+            key_from_name(key).is_none() && timestamp.parse::<f64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
+#[test]
+fn input_recording_text_round_trip_test() {
+    let timestamp = std::time::Duration::from_secs_f64(0.5);
+    let events = [
+        KappEvent::KeyDown {
+            key: Key::Return,
+            timestamp,
+        },
+        KappEvent::KeyRepeat {
+            key: Key::Return,
+            timestamp,
+        },
+        KappEvent::KeyUp {
+            key: Key::Return,
+            timestamp,
+        },
+        KappEvent::CharacterReceived { character: ' ' },
+        KappEvent::PointerDown {
+            x: 10.0,
+            y: 20.5,
+            source: PointerSource::Touch,
+            button: PointerButton::Primary,
+            id: 3,
+            timestamp,
+        },
+        KappEvent::Scroll {
+            delta_x: 0.0,
+            delta_y: -1.5,
+            window_id: kapp_platform_common::WindowId::new(std::ptr::null_mut()),
+            timestamp,
+        },
+    ];
+    let mut recording = InputRecording {
+        seed: 7,
+        records: events.into_iter().map(Record::Event).collect(),
+    };
+    recording.records.push(Record::FixedUpdate(Some(0xabc)));
+    recording.records.push(Record::FixedUpdate(None));
+
+    let text = recording.to_text();
+    assert_eq!(text.lines().count(), 9);
+    let loaded = InputRecording::from_text(&text).unwrap();
+    assert_eq!(loaded.seed, 7);
+    assert_eq!(loaded.to_text(), text);
+
+    // Unknown keys are skipped, but malformed lines are still rejected.
+    let loaded = InputRecording::from_text("seed 1\nKeyDown NotAKey 0.5\nfixed_update").unwrap();
+    assert_eq!(loaded.records.len(), 1);
+    assert_eq!(
+        InputRecording::from_text("seed 1\nKeyDown Space").err(),
+        Some(2)
+    );
+}
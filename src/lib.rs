@@ -10,6 +10,11 @@
 mod app;
 pub use app::*;
 
+#[cfg(feature = "koi_input")]
+mod input_recording;
+#[cfg(feature = "koi_input")]
+pub use input_recording::*;
+
 pub use kmath::*;
 pub use koi_ecs::*;
 pub use koi_resources::*;